@@ -11,7 +11,7 @@ use sc_service::PartialComponents;
 use sp_keyring::Sr25519Keyring;
 
 #[cfg(feature = "try-runtime")]
-use try_runtime_cli::block_building_info::timestamp_with_aura_info;
+use try_runtime_cli::block_building_info::timestamp_with_babe_info;
 
 impl SubstrateCli for Cli {
 	fn impl_name() -> String {
@@ -187,7 +187,7 @@ pub fn run() -> sc_cli::Result<()> {
 				let task_manager =
 					sc_service::TaskManager::new(config.tokio_handle.clone(), registry)
 						.map_err(|e| sc_cli::Error::Service(sc_service::Error::Prometheus(e)))?;
-				let info_provider = timestamp_with_aura_info(6000);
+				let info_provider = timestamp_with_babe_info(6000);
 
 				Ok((
 					cmd.run::<Block, ExtendedHostFunctions<
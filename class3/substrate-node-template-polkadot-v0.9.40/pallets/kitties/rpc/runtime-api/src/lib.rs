@@ -0,0 +1,42 @@
+//! Runtime API definition for the kitties pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use sp_std::prelude::*;
+
+pub use pallet_kitties::{Acquisition, KittyId, KittyTraits, ProvenanceRecord};
+
+sp_api::decl_runtime_apis! {
+	/// `Kitty` is the runtime's `pallet_kitties::KittyOf`, whose name bound is set by the runtime.
+	pub trait KittiesApi<AccountId, Balance, BlockNumber, Kitty>
+	where
+		AccountId: Codec,
+		Balance: Codec,
+		BlockNumber: Codec,
+		Kitty: Codec,
+	{
+		/// The kitty stored under `kitty_id`, if any.
+		fn kitty(kitty_id: KittyId) -> Option<Kitty>;
+		/// Ids of all kitties owned by `account`.
+		fn kitties_of(account: AccountId) -> Vec<KittyId>;
+		/// The parents of a bred kitty.
+		fn parents(kitty_id: KittyId) -> Option<(KittyId, KittyId)>;
+		/// Every listed kitty together with its asking price.
+		fn on_sale() -> Vec<(KittyId, Balance)>;
+		/// The id the next created or bred kitty will get.
+		fn next_kitty_id() -> KittyId;
+		/// Blocks left until the kitty can breed again, zero if it can breed now.
+		fn breeding_cooldown(kitty_id: KittyId) -> BlockNumber;
+		/// The traits decoded from the kitty's DNA.
+		fn kitty_traits(kitty_id: KittyId) -> Option<KittyTraits>;
+		/// How rare the kitty's traits are among the living kitties.
+		fn rarity_score(kitty_id: KittyId) -> Option<u64>;
+		/// The latest owners of the kitty, oldest first.
+		fn provenance(kitty_id: KittyId) -> Vec<ProvenanceRecord<AccountId, BlockNumber>>;
+		/// Ancestors of the kitty up to `depth` generations back, nearest generation first.
+		fn ancestors(kitty_id: KittyId, depth: u32) -> Vec<KittyId>;
+		/// All descendants of the kitty, nearest generation first.
+		fn descendants(kitty_id: KittyId) -> Vec<KittyId>;
+	}
+}
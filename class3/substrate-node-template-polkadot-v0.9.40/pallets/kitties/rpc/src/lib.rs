@@ -0,0 +1,222 @@
+//! RPC interface for the kitties pallet.
+
+use std::sync::Arc;
+
+use codec::Codec;
+use jsonrpsee::{
+	core::{DeserializeOwned, Error as JsonRpseeError, RpcResult, Serialize},
+	proc_macros::rpc,
+	types::error::{CallError, ErrorObject},
+};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::traits::Block as BlockT;
+
+pub use pallet_kitties_rpc_runtime_api::{
+	Acquisition, KittiesApi as KittiesRuntimeApi, KittyId, KittyTraits, ProvenanceRecord,
+};
+
+#[rpc(client, server)]
+pub trait KittiesApi<BlockHash, AccountId, Balance, BlockNumber, Kitty> {
+	#[method(name = "kitties_kitty")]
+	fn kitty(&self, kitty_id: KittyId, at: Option<BlockHash>) -> RpcResult<Option<Kitty>>;
+
+	#[method(name = "kitties_kittiesOf")]
+	fn kitties_of(&self, account: AccountId, at: Option<BlockHash>) -> RpcResult<Vec<KittyId>>;
+
+	#[method(name = "kitties_parents")]
+	fn parents(
+		&self,
+		kitty_id: KittyId,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<(KittyId, KittyId)>>;
+
+	#[method(name = "kitties_onSale")]
+	fn on_sale(&self, at: Option<BlockHash>) -> RpcResult<Vec<(KittyId, Balance)>>;
+
+	#[method(name = "kitties_nextKittyId")]
+	fn next_kitty_id(&self, at: Option<BlockHash>) -> RpcResult<KittyId>;
+
+	#[method(name = "kitties_breedingCooldown")]
+	fn breeding_cooldown(&self, kitty_id: KittyId, at: Option<BlockHash>) -> RpcResult<BlockNumber>;
+
+	#[method(name = "kitties_traits")]
+	fn kitty_traits(
+		&self,
+		kitty_id: KittyId,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<KittyTraits>>;
+
+	#[method(name = "kitties_rarityScore")]
+	fn rarity_score(&self, kitty_id: KittyId, at: Option<BlockHash>) -> RpcResult<Option<u64>>;
+
+	#[method(name = "kitties_provenance")]
+	fn provenance(
+		&self,
+		kitty_id: KittyId,
+		at: Option<BlockHash>,
+	) -> RpcResult<Vec<ProvenanceRecord<AccountId, BlockNumber>>>;
+
+	#[method(name = "kitties_ancestors")]
+	fn ancestors(
+		&self,
+		kitty_id: KittyId,
+		depth: u32,
+		at: Option<BlockHash>,
+	) -> RpcResult<Vec<KittyId>>;
+
+	#[method(name = "kitties_descendants")]
+	fn descendants(&self, kitty_id: KittyId, at: Option<BlockHash>) -> RpcResult<Vec<KittyId>>;
+}
+
+/// Provides RPC methods to query the kitties pallet.
+pub struct Kitties<C, P> {
+	client: Arc<C>,
+	_marker: std::marker::PhantomData<P>,
+}
+
+impl<C, P> Kitties<C, P> {
+	/// Create new `Kitties` with the given reference to the client.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+/// Error code for failures inside the runtime API call.
+const RUNTIME_ERROR: i32 = 1;
+
+fn runtime_error_into_rpc_err(err: impl std::fmt::Debug) -> JsonRpseeError {
+	CallError::Custom(ErrorObject::owned(
+		RUNTIME_ERROR,
+		"Runtime error",
+		Some(format!("{:?}", err)),
+	))
+	.into()
+}
+
+impl<C, Block, AccountId, Balance, BlockNumber, Kitty>
+	KittiesApiServer<<Block as BlockT>::Hash, AccountId, Balance, BlockNumber, Kitty>
+	for Kitties<C, Block>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: KittiesRuntimeApi<Block, AccountId, Balance, BlockNumber, Kitty>,
+	AccountId: Codec + DeserializeOwned + Serialize + Send + Sync + 'static,
+	Balance: Codec + Serialize + Send + Sync + 'static,
+	BlockNumber: Codec + Serialize + Send + Sync + 'static,
+	Kitty: Codec + Serialize + Send + Sync + 'static,
+{
+	fn kitty(
+		&self,
+		kitty_id: KittyId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Option<Kitty>> {
+		let api = self.client.runtime_api();
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		api.kitty(at, kitty_id).map_err(runtime_error_into_rpc_err)
+	}
+
+	fn kitties_of(
+		&self,
+		account: AccountId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Vec<KittyId>> {
+		let api = self.client.runtime_api();
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		api.kitties_of(at, account).map_err(runtime_error_into_rpc_err)
+	}
+
+	fn parents(
+		&self,
+		kitty_id: KittyId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Option<(KittyId, KittyId)>> {
+		let api = self.client.runtime_api();
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		api.parents(at, kitty_id).map_err(runtime_error_into_rpc_err)
+	}
+
+	fn on_sale(&self, at: Option<<Block as BlockT>::Hash>) -> RpcResult<Vec<(KittyId, Balance)>> {
+		let api = self.client.runtime_api();
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		api.on_sale(at).map_err(runtime_error_into_rpc_err)
+	}
+
+	fn next_kitty_id(&self, at: Option<<Block as BlockT>::Hash>) -> RpcResult<KittyId> {
+		let api = self.client.runtime_api();
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		api.next_kitty_id(at).map_err(runtime_error_into_rpc_err)
+	}
+
+	fn breeding_cooldown(
+		&self,
+		kitty_id: KittyId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<BlockNumber> {
+		let api = self.client.runtime_api();
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		api.breeding_cooldown(at, kitty_id).map_err(runtime_error_into_rpc_err)
+	}
+
+	fn kitty_traits(
+		&self,
+		kitty_id: KittyId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Option<KittyTraits>> {
+		let api = self.client.runtime_api();
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		api.kitty_traits(at, kitty_id).map_err(runtime_error_into_rpc_err)
+	}
+
+	fn rarity_score(
+		&self,
+		kitty_id: KittyId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Option<u64>> {
+		let api = self.client.runtime_api();
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		api.rarity_score(at, kitty_id).map_err(runtime_error_into_rpc_err)
+	}
+
+	fn provenance(
+		&self,
+		kitty_id: KittyId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Vec<ProvenanceRecord<AccountId, BlockNumber>>> {
+		let api = self.client.runtime_api();
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		api.provenance(at, kitty_id).map_err(runtime_error_into_rpc_err)
+	}
+
+	fn ancestors(
+		&self,
+		kitty_id: KittyId,
+		depth: u32,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Vec<KittyId>> {
+		let api = self.client.runtime_api();
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		api.ancestors(at, kitty_id, depth).map_err(runtime_error_into_rpc_err)
+	}
+
+	fn descendants(
+		&self,
+		kitty_id: KittyId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Vec<KittyId>> {
+		let api = self.client.runtime_api();
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		api.descendants(at, kitty_id).map_err(runtime_error_into_rpc_err)
+	}
+}
@@ -0,0 +1,415 @@
+//! Benchmarking setup for pallet-kitties
+
+use super::*;
+
+#[allow(unused)]
+use crate::Pallet as KittiesModule;
+use frame_benchmarking::v1::{account, benchmarks, whitelisted_caller};
+use frame_support::traits::{Currency, Get};
+use frame_system::RawOrigin;
+use sp_runtime::{
+	traits::{Bounded, Hash},
+	DispatchResult,
+};
+use sp_std::{vec, vec::Vec};
+
+/// A name of `MaxNameLength` bytes, the largest name deposit.
+fn kitty_name<T: Config>() -> Vec<u8> {
+	vec![b'a'; T::MaxNameLength::get() as usize]
+}
+
+fn funded_account<T: Config>(name: &'static str, index: u32) -> T::AccountId {
+	let who: T::AccountId = account(name, index, 0);
+	T::Currency::make_free_balance_be(&who, BalanceOf::<T>::max_value() / 2u32.into());
+	who
+}
+
+fn funded_caller<T: Config>() -> T::AccountId {
+	let caller: T::AccountId = whitelisted_caller();
+	T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value() / 2u32.into());
+	caller
+}
+
+/// Add offers on `kitty_id` up to `MaxOffersPerKitty`, leaving room for one more, and move to the
+/// block they expire in, so the call under test releases as many as it can.
+fn expired_offers<T: Config>(kitty_id: KittyId) -> DispatchResult {
+	let now = frame_system::Pallet::<T>::block_number();
+	let expiry = now + 1u32.into();
+	for i in 1 .. T::MaxOffersPerKitty::get() {
+		let offerer = funded_account::<T>("offerer", i);
+		KittiesModule::<T>::make_offer(RawOrigin::Signed(offerer).into(), kitty_id, T::KittyPrice::get(), expiry)?;
+	}
+	frame_system::Pallet::<T>::set_block_number(expiry);
+	Ok(())
+}
+
+benchmarks! {
+	create {
+		let caller = funded_caller::<T>();
+	}: _(RawOrigin::Signed(caller.clone()), kitty_name::<T>())
+	verify {
+		assert_eq!(KittyOwner::<T>::get(0), Some(caller));
+	}
+
+	breed {
+		let caller = funded_caller::<T>();
+		KittiesModule::<T>::create(RawOrigin::Signed(caller.clone()).into(), kitty_name::<T>())?;
+		KittiesModule::<T>::create(RawOrigin::Signed(caller.clone()).into(), kitty_name::<T>())?;
+	}: _(RawOrigin::Signed(caller.clone()), 0, 1, kitty_name::<T>())
+	verify {
+		assert_eq!(KittyParents::<T>::get(2), Some((0, 1)));
+		assert!(KittyChildren::<T>::contains_key(0, 2));
+	}
+
+	transfer {
+		let caller = funded_caller::<T>();
+		let to: T::AccountId = account("to", 0, 0);
+		KittiesModule::<T>::create(RawOrigin::Signed(caller.clone()).into(), kitty_name::<T>())?;
+	}: _(RawOrigin::Signed(caller), to.clone(), 0)
+	verify {
+		assert_eq!(KittyOwner::<T>::get(0), Some(to));
+	}
+
+	sale {
+		let caller = funded_caller::<T>();
+		let price = T::KittyPrice::get();
+		let expiry = frame_system::Pallet::<T>::block_number() + 10u32.into();
+		KittiesModule::<T>::create(RawOrigin::Signed(caller.clone()).into(), kitty_name::<T>())?;
+	}: _(RawOrigin::Signed(caller), 0, price, Some(expiry))
+	verify {
+		assert_eq!(KittyOnSale::<T>::get(0), Some(price));
+	}
+
+	buy {
+		let seller = funded_account::<T>("seller", 0);
+		let caller = funded_caller::<T>();
+		let creator = funded_account::<T>("creator", 0);
+		let price = T::KittyPrice::get();
+		// A secondary sale also pays the creator royalty.
+		KittiesModule::<T>::create(RawOrigin::Signed(creator.clone()).into(), kitty_name::<T>())?;
+		KittiesModule::<T>::transfer(RawOrigin::Signed(creator).into(), seller.clone(), 0)?;
+		KittiesModule::<T>::sale(RawOrigin::Signed(seller).into(), 0, price, None)?;
+	}: _(RawOrigin::Signed(caller.clone()), 0, price)
+	verify {
+		assert_eq!(KittyOwner::<T>::get(0), Some(caller));
+	}
+
+	update_price {
+		let caller = funded_caller::<T>();
+		let price = T::KittyPrice::get();
+		KittiesModule::<T>::create(RawOrigin::Signed(caller.clone()).into(), kitty_name::<T>())?;
+		KittiesModule::<T>::sale(RawOrigin::Signed(caller.clone()).into(), 0, price, None)?;
+	}: _(RawOrigin::Signed(caller), 0, price + price)
+	verify {
+		assert_eq!(KittyOnSale::<T>::get(0), Some(price + price));
+	}
+
+	create_auction {
+		let caller = funded_caller::<T>();
+		let min_bid = T::KittyPrice::get();
+		let end_block = frame_system::Pallet::<T>::block_number() + 10u32.into();
+		KittiesModule::<T>::create(RawOrigin::Signed(caller.clone()).into(), kitty_name::<T>())?;
+	}: _(RawOrigin::Signed(caller), 0, min_bid, end_block)
+	verify {
+		assert!(Auctions::<T>::contains_key(0));
+	}
+
+	bid {
+		let seller = funded_account::<T>("seller", 0);
+		let outbid = funded_account::<T>("bidder", 0);
+		let caller = funded_caller::<T>();
+		let min_bid = T::KittyPrice::get();
+		let end_block = frame_system::Pallet::<T>::block_number() + 10u32.into();
+		KittiesModule::<T>::create(RawOrigin::Signed(seller.clone()).into(), kitty_name::<T>())?;
+		KittiesModule::<T>::create_auction(RawOrigin::Signed(seller).into(), 0, min_bid, end_block)?;
+		KittiesModule::<T>::bid(RawOrigin::Signed(outbid).into(), 0, min_bid)?;
+	}: _(RawOrigin::Signed(caller.clone()), 0, min_bid + min_bid)
+	verify {
+		assert_eq!(Auctions::<T>::get(0).unwrap().best_bid, Some((caller, min_bid + min_bid)));
+	}
+
+	settle_auction {
+		let seller = funded_account::<T>("seller", 0);
+		let caller = funded_caller::<T>();
+		let creator = funded_account::<T>("creator", 0);
+		let min_bid = T::KittyPrice::get();
+		let end_block = frame_system::Pallet::<T>::block_number() + 10u32.into();
+		// A secondary sale also pays the creator royalty.
+		KittiesModule::<T>::create(RawOrigin::Signed(creator.clone()).into(), kitty_name::<T>())?;
+		KittiesModule::<T>::transfer(RawOrigin::Signed(creator).into(), seller.clone(), 0)?;
+		KittiesModule::<T>::create_auction(RawOrigin::Signed(seller).into(), 0, min_bid, end_block)?;
+		KittiesModule::<T>::bid(RawOrigin::Signed(caller.clone()).into(), 0, min_bid)?;
+	}: {
+		KittiesModule::<T>::settle_auction(0);
+	}
+	verify {
+		assert_eq!(KittyOwner::<T>::get(0), Some(caller));
+	}
+
+	make_offer {
+		let owner = funded_account::<T>("owner", 0);
+		let caller = funded_caller::<T>();
+		let amount = T::KittyPrice::get();
+		let expiry = frame_system::Pallet::<T>::block_number() + 10u32.into();
+		KittiesModule::<T>::create(RawOrigin::Signed(owner).into(), kitty_name::<T>())?;
+		expired_offers::<T>(0)?;
+	}: _(RawOrigin::Signed(caller.clone()), 0, amount, expiry)
+	verify {
+		assert!(Offers::<T>::get(0).iter().any(|offer| offer.offerer == caller));
+	}
+
+	withdraw_offer {
+		let owner = funded_account::<T>("owner", 0);
+		let caller = funded_caller::<T>();
+		let amount = T::KittyPrice::get();
+		let expiry = frame_system::Pallet::<T>::block_number() + 10u32.into();
+		KittiesModule::<T>::create(RawOrigin::Signed(owner).into(), kitty_name::<T>())?;
+		KittiesModule::<T>::make_offer(RawOrigin::Signed(caller.clone()).into(), 0, amount, expiry)?;
+		expired_offers::<T>(0)?;
+	}: _(RawOrigin::Signed(caller.clone()), 0)
+	verify {
+		assert!(!Offers::<T>::contains_key(0));
+	}
+
+	accept_offer {
+		let caller = funded_caller::<T>();
+		let offerer = funded_account::<T>("offerer", 0);
+		let creator = funded_account::<T>("creator", 0);
+		let amount = T::KittyPrice::get();
+		let expiry = frame_system::Pallet::<T>::block_number() + 10u32.into();
+		// A secondary sale also pays the creator royalty.
+		KittiesModule::<T>::create(RawOrigin::Signed(creator.clone()).into(), kitty_name::<T>())?;
+		KittiesModule::<T>::transfer(RawOrigin::Signed(creator).into(), caller.clone(), 0)?;
+		KittiesModule::<T>::sale(RawOrigin::Signed(caller.clone()).into(), 0, amount, None)?;
+		KittiesModule::<T>::make_offer(RawOrigin::Signed(offerer.clone()).into(), 0, amount, expiry)?;
+		expired_offers::<T>(0)?;
+	}: _(RawOrigin::Signed(caller), 0, offerer.clone())
+	verify {
+		assert_eq!(KittyOwner::<T>::get(0), Some(offerer));
+		assert!(!Offers::<T>::contains_key(0));
+	}
+
+	cancel_sale {
+		let caller = funded_caller::<T>();
+		let price = T::KittyPrice::get();
+		let expiry = frame_system::Pallet::<T>::block_number() + 10u32.into();
+		KittiesModule::<T>::create(RawOrigin::Signed(caller.clone()).into(), kitty_name::<T>())?;
+		KittiesModule::<T>::sale(RawOrigin::Signed(caller.clone()).into(), 0, price, Some(expiry))?;
+	}: _(RawOrigin::Signed(caller), 0)
+	verify {
+		assert_eq!(KittyOnSale::<T>::get(0), None);
+	}
+
+	burn {
+		let caller = funded_caller::<T>();
+		let expiry = frame_system::Pallet::<T>::block_number() + 10u32.into();
+		KittiesModule::<T>::create(RawOrigin::Signed(caller.clone()).into(), kitty_name::<T>())?;
+		KittiesModule::<T>::create(RawOrigin::Signed(caller.clone()).into(), kitty_name::<T>())?;
+		KittiesModule::<T>::breed(RawOrigin::Signed(caller.clone()).into(), 0, 1, kitty_name::<T>())?;
+		KittiesModule::<T>::sale(RawOrigin::Signed(caller.clone()).into(), 2, T::KittyPrice::get(), Some(expiry))?;
+		for i in 0 .. T::MaxOffersPerKitty::get() {
+			let offerer = funded_account::<T>("offerer", i);
+			KittiesModule::<T>::make_offer(RawOrigin::Signed(offerer).into(), 2, T::KittyPrice::get(), expiry)?;
+		}
+	}: _(RawOrigin::Signed(caller), 2)
+	verify {
+		assert_eq!(Kitties::<T>::get(2), None);
+		assert!(!KittyChildren::<T>::contains_key(0, 2));
+	}
+
+	rename {
+		let caller = funded_caller::<T>();
+		let other = funded_account::<T>("other", 0);
+		KittiesModule::<T>::create(RawOrigin::Signed(other.clone()).into(), kitty_name::<T>())?;
+		KittiesModule::<T>::transfer(RawOrigin::Signed(other).into(), caller.clone(), 0)?;
+	}: _(RawOrigin::Signed(caller.clone()), 0, kitty_name::<T>())
+	verify {
+		assert_eq!(NameDeposits::<T>::get(0).map(|(depositor, _)| depositor), Some(caller));
+	}
+
+	approve {
+		let caller = funded_caller::<T>();
+		let delegate: T::AccountId = account("delegate", 0, 0);
+		KittiesModule::<T>::create(RawOrigin::Signed(caller.clone()).into(), kitty_name::<T>())?;
+	}: _(RawOrigin::Signed(caller), 0, Some(delegate.clone()))
+	verify {
+		assert_eq!(KittyApprovals::<T>::get(0), Some(delegate));
+	}
+
+	set_approval_for_all {
+		let caller = funded_caller::<T>();
+		let operator: T::AccountId = account("operator", 0, 0);
+	}: _(RawOrigin::Signed(caller.clone()), operator.clone(), true)
+	verify {
+		assert!(OperatorApprovals::<T>::contains_key(&caller, &operator));
+	}
+
+	transfer_from {
+		let owner = funded_account::<T>("owner", 0);
+		let caller = funded_caller::<T>();
+		let to = funded_account::<T>("to", 0);
+		let expiry = frame_system::Pallet::<T>::block_number() + 10u32.into();
+		KittiesModule::<T>::create(RawOrigin::Signed(owner.clone()).into(), kitty_name::<T>())?;
+		KittiesModule::<T>::sale(RawOrigin::Signed(owner.clone()).into(), 0, T::KittyPrice::get(), Some(expiry))?;
+		KittiesModule::<T>::set_approval_for_all(RawOrigin::Signed(owner.clone()).into(), caller.clone(), true)?;
+	}: _(RawOrigin::Signed(caller), owner, to.clone(), 0)
+	verify {
+		assert_eq!(KittyOwner::<T>::get(0), Some(to));
+	}
+
+	offer_rental {
+		let caller = funded_caller::<T>();
+		KittiesModule::<T>::create(RawOrigin::Signed(caller.clone()).into(), kitty_name::<T>())?;
+	}: _(RawOrigin::Signed(caller), 0, T::KittyPrice::get(), 10u32.into())
+	verify {
+		assert!(RentalOffers::<T>::contains_key(0));
+	}
+
+	cancel_rental_offer {
+		let caller = funded_caller::<T>();
+		KittiesModule::<T>::create(RawOrigin::Signed(caller.clone()).into(), kitty_name::<T>())?;
+		KittiesModule::<T>::offer_rental(RawOrigin::Signed(caller.clone()).into(), 0, T::KittyPrice::get(), 10u32.into())?;
+	}: _(RawOrigin::Signed(caller), 0)
+	verify {
+		assert!(!RentalOffers::<T>::contains_key(0));
+	}
+
+	rent {
+		let owner = funded_account::<T>("owner", 0);
+		let caller = funded_caller::<T>();
+		KittiesModule::<T>::create(RawOrigin::Signed(owner.clone()).into(), kitty_name::<T>())?;
+		KittiesModule::<T>::offer_rental(RawOrigin::Signed(owner).into(), 0, T::KittyPrice::get(), 10u32.into())?;
+	}: _(RawOrigin::Signed(caller.clone()), 0, 10u32.into())
+	verify {
+		assert_eq!(Rentals::<T>::get(0).map(|rental| rental.renter), Some(caller));
+	}
+
+	offer_siring {
+		let caller = funded_caller::<T>();
+		KittiesModule::<T>::create(RawOrigin::Signed(caller.clone()).into(), kitty_name::<T>())?;
+	}: _(RawOrigin::Signed(caller), 0, T::KittyPrice::get())
+	verify {
+		assert_eq!(SiringOffers::<T>::get(0), Some(T::KittyPrice::get()));
+	}
+
+	cancel_siring_offer {
+		let caller = funded_caller::<T>();
+		KittiesModule::<T>::create(RawOrigin::Signed(caller.clone()).into(), kitty_name::<T>())?;
+		KittiesModule::<T>::offer_siring(RawOrigin::Signed(caller.clone()).into(), 0, T::KittyPrice::get())?;
+	}: _(RawOrigin::Signed(caller), 0)
+	verify {
+		assert_eq!(SiringOffers::<T>::get(0), None);
+	}
+
+	breed_with_sire {
+		let sire_owner = funded_account::<T>("sire_owner", 0);
+		let caller = funded_caller::<T>();
+		KittiesModule::<T>::create(RawOrigin::Signed(caller.clone()).into(), kitty_name::<T>())?;
+		KittiesModule::<T>::create(RawOrigin::Signed(sire_owner.clone()).into(), kitty_name::<T>())?;
+		KittiesModule::<T>::offer_siring(RawOrigin::Signed(sire_owner).into(), 1, T::KittyPrice::get())?;
+	}: _(RawOrigin::Signed(caller.clone()), 0, 1, T::KittyPrice::get(), kitty_name::<T>())
+	verify {
+		assert_eq!(KittyOwner::<T>::get(2), Some(caller));
+	}
+
+	commit_mint {
+		let caller = funded_caller::<T>();
+		KittiesModule::<T>::create(RawOrigin::Signed(caller.clone()).into(), kitty_name::<T>())?;
+		KittiesModule::<T>::create(RawOrigin::Signed(caller.clone()).into(), kitty_name::<T>())?;
+		let commitment = T::Hashing::hash(&[7u8; 32]);
+	}: _(RawOrigin::Signed(caller.clone()), commitment, Some((0, 1)), kitty_name::<T>())
+	verify {
+		assert!(Commitments::<T>::contains_key(&caller, commitment));
+	}
+
+	reveal_mint {
+		let caller = funded_caller::<T>();
+		KittiesModule::<T>::create(RawOrigin::Signed(caller.clone()).into(), kitty_name::<T>())?;
+		KittiesModule::<T>::create(RawOrigin::Signed(caller.clone()).into(), kitty_name::<T>())?;
+		let commitment = T::Hashing::hash(&[7u8; 32]);
+		KittiesModule::<T>::commit_mint(
+			RawOrigin::Signed(caller.clone()).into(),
+			commitment,
+			Some((0, 1)),
+			kitty_name::<T>(),
+		)?;
+		let now = frame_system::Pallet::<T>::block_number();
+		frame_system::Pallet::<T>::set_block_number(now + T::RevealDelay::get());
+	}: _(RawOrigin::Signed(caller.clone()), [7u8; 32])
+	verify {
+		assert_eq!(KittyOwner::<T>::get(2), Some(caller));
+	}
+
+	challenge {
+		let opponent = funded_account::<T>("opponent", 0);
+		let caller = funded_caller::<T>();
+		KittiesModule::<T>::create(RawOrigin::Signed(caller.clone()).into(), kitty_name::<T>())?;
+		KittiesModule::<T>::create(RawOrigin::Signed(opponent).into(), kitty_name::<T>())?;
+	}: _(RawOrigin::Signed(caller), 0, 1, T::KittyPrice::get())
+	verify {
+		assert!(Challenges::<T>::contains_key(1));
+	}
+
+	accept_challenge {
+		let challenger = funded_account::<T>("challenger", 0);
+		let caller = funded_caller::<T>();
+		KittiesModule::<T>::create(RawOrigin::Signed(challenger.clone()).into(), kitty_name::<T>())?;
+		KittiesModule::<T>::create(RawOrigin::Signed(caller.clone()).into(), kitty_name::<T>())?;
+		KittiesModule::<T>::challenge(RawOrigin::Signed(challenger).into(), 0, 1, T::KittyPrice::get())?;
+	}: _(RawOrigin::Signed(caller), 1)
+	verify {
+		assert!(AcceptedChallenges::<T>::contains_key(1));
+	}
+
+	fight_battle {
+		let challenger = funded_account::<T>("challenger", 0);
+		let caller = funded_caller::<T>();
+		KittiesModule::<T>::create(RawOrigin::Signed(challenger.clone()).into(), kitty_name::<T>())?;
+		KittiesModule::<T>::create(RawOrigin::Signed(caller.clone()).into(), kitty_name::<T>())?;
+		KittiesModule::<T>::challenge(RawOrigin::Signed(challenger).into(), 0, 1, T::KittyPrice::get())?;
+		KittiesModule::<T>::accept_challenge(RawOrigin::Signed(caller).into(), 1)?;
+	}: {
+		KittiesModule::<T>::fight_battle(1);
+	}
+	verify {
+		assert!(!Challenges::<T>::contains_key(1));
+	}
+
+	cancel_challenge {
+		let opponent = funded_account::<T>("opponent", 0);
+		let caller = funded_caller::<T>();
+		KittiesModule::<T>::create(RawOrigin::Signed(caller.clone()).into(), kitty_name::<T>())?;
+		KittiesModule::<T>::create(RawOrigin::Signed(opponent).into(), kitty_name::<T>())?;
+		KittiesModule::<T>::challenge(RawOrigin::Signed(caller.clone()).into(), 0, 1, T::KittyPrice::get())?;
+	}: _(RawOrigin::Signed(caller), 1)
+	verify {
+		assert!(!Challenges::<T>::contains_key(1));
+	}
+
+	batch_transfer {
+		let n in 1 .. T::MaxBatch::get();
+		let caller = funded_caller::<T>();
+		let to: T::AccountId = account("to", 0, 0);
+		for _ in 0..n {
+			KittiesModule::<T>::create(RawOrigin::Signed(caller.clone()).into(), kitty_name::<T>())?;
+		}
+		let transfers: Vec<_> = (0..n).map(|kitty_id| (to.clone(), kitty_id)).collect();
+	}: _(RawOrigin::Signed(caller), transfers)
+	verify {
+		assert_eq!(KittyOwner::<T>::get(n - 1), Some(to));
+	}
+
+	batch_sale {
+		let n in 1 .. T::MaxBatch::get();
+		let caller = funded_caller::<T>();
+		for _ in 0..n {
+			KittiesModule::<T>::create(RawOrigin::Signed(caller.clone()).into(), kitty_name::<T>())?;
+		}
+		let listings: Vec<_> = (0..n).map(|kitty_id| (kitty_id, T::KittyPrice::get())).collect();
+	}: _(RawOrigin::Signed(caller), listings)
+	verify {
+		assert_eq!(KittyOnSale::<T>::get(n - 1), Some(T::KittyPrice::get()));
+	}
+
+	impl_benchmark_test_suite!(KittiesModule, crate::mock::new_test_ext(), crate::mock::Test);
+}
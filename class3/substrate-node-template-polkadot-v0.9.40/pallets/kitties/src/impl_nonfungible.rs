@@ -0,0 +1,103 @@
+//! Implementations of the `nonfungible` traits for the kitties pallet.
+//!
+//! These are called from other pallets rather than dispatched, so each mutating method runs in
+//! its own storage layer: a failure part way through leaves no partial writes behind.
+
+use super::*;
+use frame_support::{
+	ensure,
+	storage::with_storage_layer,
+	traits::tokens::nonfungible::{Inspect, Mutate, Transfer},
+	BoundedVec,
+};
+use sp_runtime::{traits::Zero, DispatchResult, TokenError};
+use sp_std::prelude::*;
+
+/// Attribute key of the kitty dna.
+pub const DNA_KEY: &[u8] = b"dna";
+/// Attribute key of the kitty name.
+pub const NAME_KEY: &[u8] = b"name";
+
+impl<T: Config> Inspect<T::AccountId> for Pallet<T> {
+	type ItemId = KittyId;
+
+	fn owner(item: &KittyId) -> Option<T::AccountId> {
+		KittyOwner::<T>::get(item)
+	}
+
+	/// `dna` and `name` of the kitty.
+	fn attribute(item: &KittyId, key: &[u8]) -> Option<Vec<u8>> {
+		let kitty = Kitties::<T>::get(item)?;
+		match key {
+			DNA_KEY => Some(kitty.dna.to_vec()),
+			NAME_KEY => Some(kitty.name.into_inner()),
+			_ => None,
+		}
+	}
+
+	fn can_transfer(item: &KittyId) -> bool {
+		KittyOwner::<T>::contains_key(item) &&
+			!Auctions::<T>::contains_key(item) &&
+			!Rentals::<T>::contains_key(item) &&
+			!Pallet::<T>::in_battle(*item)
+	}
+}
+
+impl<T: Config> Transfer<T::AccountId> for Pallet<T> {
+	fn transfer(item: &KittyId, destination: &T::AccountId) -> DispatchResult {
+		with_storage_layer(|| {
+			let owner = KittyOwner::<T>::get(item).ok_or(Error::<T>::InvalidKittyId)?;
+			Pallet::<T>::do_transfer(owner, destination.clone(), *item)
+		})
+	}
+}
+
+impl<T: Config> Mutate<T::AccountId> for Pallet<T> {
+	/// Mint an unnamed kitty with random dna. Ids are sequential, so `item` must be the next
+	/// kitty id. No `KittyPrice` is charged, so burning the kitty refunds nothing.
+	fn mint_into(item: &KittyId, who: &T::AccountId) -> DispatchResult {
+		with_storage_layer(|| {
+			ensure!(*item == Pallet::<T>::next_kitty_id(), Error::<T>::InvalidKittyId);
+
+			let dna = Pallet::<T>::random_value(who)?;
+			let (kitty_id, kitty) = Pallet::<T>::insert_new_kitty(
+				who,
+				dna,
+				BoundedVec::default(),
+				None,
+				Acquisition::Created,
+				Zero::zero(),
+			)?;
+
+			Pallet::<T>::deposit_event(Event::KittyCreated { who: who.clone(), kitty_id, kitty });
+
+			Ok(())
+		})
+	}
+
+	/// Burn a kitty without refunding any of `KittyPrice`.
+	fn burn(item: &KittyId, maybe_check_owner: Option<&T::AccountId>) -> DispatchResult {
+		let owner = KittyOwner::<T>::get(item).ok_or(Error::<T>::InvalidKittyId)?;
+		if let Some(check_owner) = maybe_check_owner {
+			ensure!(*check_owner == owner, Error::<T>::NotOwner);
+		}
+		ensure!(!Auctions::<T>::contains_key(item), Error::<T>::KittyInAuction);
+		ensure!(!Rentals::<T>::contains_key(item), Error::<T>::KittyRented);
+		ensure!(!Pallet::<T>::in_battle(*item), Error::<T>::KittyInBattle);
+
+		Pallet::<T>::do_burn(owner, *item, Zero::zero());
+
+		Ok(())
+	}
+
+	/// Only `name` can be set. The name deposit is reserved from the owner.
+	fn set_attribute(item: &KittyId, key: &[u8], value: &[u8]) -> DispatchResult {
+		ensure!(key == NAME_KEY, TokenError::Unsupported);
+
+		with_storage_layer(|| {
+			let owner = KittyOwner::<T>::get(item).ok_or(Error::<T>::InvalidKittyId)?;
+			let name = value.to_vec().try_into().map_err(|_| Error::<T>::NameTooLong)?;
+			Pallet::<T>::do_rename(owner, *item, name)
+		})
+	}
+}
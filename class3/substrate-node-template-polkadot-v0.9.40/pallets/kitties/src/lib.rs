@@ -2,7 +2,12 @@
 
 pub use pallet::*;
 
-mod migrations;
+mod impl_nonfungible;
+pub mod migrations;
+pub mod traits;
+pub mod weights;
+pub use traits::{OnKittyCreated, OnKittyTransfer};
+pub use weights::*;
 
 #[cfg(test)]
 mod mock;
@@ -10,29 +15,211 @@ mod mock;
 #[cfg(test)]
 mod tests;
 
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
 #[frame_support::pallet]
 pub mod pallet {
 	use frame_support::pallet_prelude::*;
 	use frame_system::pallet_prelude::*;
 
 	use sp_io::hashing::blake2_128;
-	use frame_support::traits::{Randomness, Currency, ExistenceRequirement, StorageVersion};
+	use frame_support::traits::{
+		BalanceStatus, Currency, ExistenceRequirement, Randomness, ReservableCurrency, StorageVersion,
+	};
 	use frame_support::PalletId;
-	use sp_runtime::traits::AccountIdConversion;
+	use sp_runtime::{
+		traits::{AccountIdConversion, Hash, One, Saturating, UniqueSaturatedInto, Zero},
+		Perbill,
+	};
+	use sp_std::{
+		collections::{btree_set::BTreeSet, vec_deque::VecDeque},
+		prelude::*,
+	};
+	#[cfg(feature = "std")]
+	use serde::{Deserialize, Serialize};
 
-	use crate::migrations;
+	use crate::WeightInfo;
 
 	pub type KittyId = u32;
 	pub type BalanceOf<T> = <<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
 
-	#[derive(Encode, Decode, Clone, Copy, RuntimeDebug, PartialEq, Eq, Default, TypeInfo, MaxEncodedLen)]
+	#[derive(
+		Encode, Decode, CloneNoBound, RuntimeDebugNoBound, PartialEqNoBound, EqNoBound, TypeInfo, MaxEncodedLen,
+	)]
+	#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+	#[cfg_attr(feature = "std", serde(bound = ""))]
+	#[codec(mel_bound())]
+	#[scale_info(skip_type_params(MaxNameLength))]
 	// pub struct Kitty(pub [u8; 16]);
-	pub struct Kitty {
+	pub struct Kitty<MaxNameLength: Get<u32>> {
 		pub dna: [u8; 16],
-		pub name: [u8; 4],
+		pub name: BoundedVec<u8, MaxNameLength>,
+	}
+
+	pub type KittyOf<T> = Kitty<<T as Config>::MaxNameLength>;
+
+	/// The DNA byte each trait of `KittyTraits` is read from and its number of variants, in field
+	/// order.
+	pub const TRAIT_SCHEMA: [(usize, u8); 6] = [(0, 16), (1, 8), (2, 16), (3, 16), (4, 4), (5, 8)];
+
+	/// Scale of the per-trait rarity summed up by `rarity_score`.
+	pub const RARITY_SCALE: u64 = 100;
+
+	/// The visible traits of a kitty, decoded from its DNA by `TRAIT_SCHEMA`.
+	#[derive(Encode, Decode, Clone, Copy, RuntimeDebug, PartialEq, Eq, TypeInfo, MaxEncodedLen)]
+	#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+	pub struct KittyTraits {
+		pub fur_color: u8,
+		pub pattern: u8,
+		pub pattern_color: u8,
+		pub eye_color: u8,
+		pub eye_shape: u8,
+		pub mouth: u8,
+	}
+
+	impl KittyTraits {
+		pub fn from_dna(dna: &[u8; 16]) -> Self {
+			let [fur_color, pattern, pattern_color, eye_color, eye_shape, mouth] =
+				TRAIT_SCHEMA.map(|(byte, variants)| dna[byte] % variants);
+			Self { fur_color, pattern, pattern_color, eye_color, eye_shape, mouth }
+		}
+
+		/// The variant of each trait, indexed like `TRAIT_SCHEMA`.
+		pub fn variants(&self) -> [u8; 6] {
+			[
+				self.fur_color,
+				self.pattern,
+				self.pattern_color,
+				self.eye_color,
+				self.eye_shape,
+				self.mouth,
+			]
+		}
+	}
+
+	/// An English auction of a kitty. The best bid is reserved from the bidder until it is
+	/// outbid or the auction is settled.
+	#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq, TypeInfo, MaxEncodedLen)]
+	pub struct Auction<AccountId, Balance, BlockNumber> {
+		pub seller: AccountId,
+		pub min_bid: Balance,
+		pub end_block: BlockNumber,
+		pub best_bid: Option<(AccountId, Balance)>,
+	}
+
+	/// An offer to buy a kitty that may not be listed. `amount` is reserved from the offerer
+	/// until the offer is withdrawn, accepted or found expired.
+	#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq, TypeInfo, MaxEncodedLen)]
+	pub struct Offer<AccountId, Balance, BlockNumber> {
+		pub offerer: AccountId,
+		pub amount: Balance,
+		pub expiry: BlockNumber,
+	}
+
+	pub type OfferOf<T> = Offer<
+		<T as frame_system::Config>::AccountId,
+		BalanceOf<T>,
+		<T as frame_system::Config>::BlockNumber,
+	>;
+
+	/// Terms under which the owner offers a kitty for rent.
+	#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq, TypeInfo, MaxEncodedLen)]
+	pub struct RentalOffer<Balance, BlockNumber> {
+		pub price_per_block: Balance,
+		pub max_duration: BlockNumber,
+	}
+
+	/// An active rental. The renter has the usage rights of the kitty until `end_block`.
+	#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq, TypeInfo, MaxEncodedLen)]
+	pub struct Rental<AccountId, BlockNumber> {
+		pub renter: AccountId,
+		pub end_block: BlockNumber,
+	}
+
+	pub type RentalOfferOf<T> = RentalOffer<BalanceOf<T>, <T as frame_system::Config>::BlockNumber>;
+
+	pub type RentalOf<T> = Rental<
+		<T as frame_system::Config>::AccountId,
+		<T as frame_system::Config>::BlockNumber,
+	>;
+
+	/// A hidden mint: a kitty created, or bred from `parents`, whose DNA comes from a secret
+	/// committed to in `block`. `deposit` is reserved until the secret is revealed or the
+	/// commitment expires.
+	#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq, TypeInfo, MaxEncodedLen)]
+	pub struct Commitment<Balance, BlockNumber, Name> {
+		pub parents: Option<(KittyId, KittyId)>,
+		pub name: Name,
+		pub block: BlockNumber,
+		pub deposit: Balance,
+	}
+
+	pub type CommitmentOf<T> = Commitment<
+		BalanceOf<T>,
+		<T as frame_system::Config>::BlockNumber,
+		BoundedVec<u8, <T as Config>::MaxNameLength>,
+	>;
+
+	/// A challenge of `kitty_id` of `challenger` against another kitty. `stake` is reserved from
+	/// the challenger until the challenge is cancelled or its battle fought.
+	#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq, TypeInfo, MaxEncodedLen)]
+	pub struct Challenge<AccountId, Balance> {
+		pub challenger: AccountId,
+		pub kitty_id: KittyId,
+		pub stake: Balance,
+	}
+
+	pub type ChallengeOf<T> = Challenge<<T as frame_system::Config>::AccountId, BalanceOf<T>>;
+
+	/// Battle record of a kitty.
+	#[derive(Encode, Decode, Clone, Default, RuntimeDebug, PartialEq, Eq, TypeInfo, MaxEncodedLen)]
+	#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+	pub struct KittyStats {
+		pub wins: u32,
+		pub losses: u32,
+		pub experience: u32,
+		pub level: u32,
+	}
+
+	/// Battle power each level adds on top of the DNA.
+	pub const LEVEL_POWER: u32 = 64;
+
+	/// How an owner came by a kitty.
+	#[derive(Encode, Decode, Clone, Copy, RuntimeDebug, PartialEq, Eq, TypeInfo, MaxEncodedLen)]
+	#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+	pub enum Acquisition {
+		/// Created, minted or given at genesis.
+		Created,
+		Bred,
+		Transferred,
+		/// Bought from a listing or sold to an offer.
+		Bought,
+		/// Won in an auction.
+		Auction,
+	}
+
+	/// An entry in the provenance log of a kitty: `owner` got the kitty in `block`.
+	#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq, TypeInfo, MaxEncodedLen)]
+	#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+	pub struct ProvenanceRecord<AccountId, BlockNumber> {
+		pub owner: AccountId,
+		pub block: BlockNumber,
+		pub acquisition: Acquisition,
 	}
 
-	const STORAGE_VSRSION: StorageVersion = StorageVersion::new(1);
+	pub type ProvenanceRecordOf<T> = ProvenanceRecord<
+		<T as frame_system::Config>::AccountId,
+		<T as frame_system::Config>::BlockNumber,
+	>;
+
+	pub type AuctionOf<T> = Auction<
+		<T as frame_system::Config>::AccountId,
+		BalanceOf<T>,
+		<T as frame_system::Config>::BlockNumber,
+	>;
+
+	const STORAGE_VSRSION: StorageVersion = StorageVersion::new(7);
 
 	#[pallet::pallet]
 	#[pallet::storage_version(STORAGE_VSRSION)]
@@ -42,11 +229,92 @@ pub mod pallet {
 	pub trait Config: frame_system::Config {
 		/// Because this pallet emits events, it depends on the runtime's definition of an event.
 		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
-		type Randomness: Randomness<Self::Hash, Self::BlockNumber>;
-		type Currency: Currency<Self::AccountId>;
+		/// Source of the randomness mixed into new DNA, such as BABE's `ParentBlockRandomness`.
+		/// Its output is public once the parent block is out, and block producers learn it epochs
+		/// earlier, so the DNA of a one-step `create` or `breed` can be worked out in advance;
+		/// `commit_mint` does not have that weakness. Minting fails while it has no output.
+		type Randomness: Randomness<Option<Self::Hash>, Self::BlockNumber>;
+		type Currency: ReservableCurrency<Self::AccountId>;
 		#[pallet::constant]
 		type KittyPrice: Get<BalanceOf<Self>>;
 		type PalletId: Get<PalletId>;
+		/// Blocks a generation 0 kitty has to rest after breeding.
+		#[pallet::constant]
+		type BaseBreedingCooldown: Get<Self::BlockNumber>;
+		/// Extra resting blocks per generation.
+		#[pallet::constant]
+		type BreedingCooldownPerGeneration: Get<Self::BlockNumber>;
+		/// Upper bound of the breeding cooldown of any generation.
+		#[pallet::constant]
+		type MaxBreedingCooldown: Get<Self::BlockNumber>;
+		/// The maximum length of a kitty name in bytes.
+		#[pallet::constant]
+		type MaxNameLength: Get<u32>;
+		/// The deposit reserved from the owner for each byte of a kitty name.
+		#[pallet::constant]
+		type NameDepositPerByte: Get<BalanceOf<Self>>;
+		/// The part of every sale price paid to the pallet account.
+		#[pallet::constant]
+		type MarketplaceFee: Get<Perbill>;
+		/// The part of the price paid to the account that minted the kitty when someone else
+		/// sells it.
+		#[pallet::constant]
+		type CreatorRoyalty: Get<Perbill>;
+		/// The part of the price paid to mint a kitty refunded from the pallet account when it is
+		/// burned.
+		#[pallet::constant]
+		type BurnRefund: Get<Perbill>;
+		/// The maximum number of kitties a single account can own.
+		#[pallet::constant]
+		type MaxKittiesOwned: Get<u32>;
+		/// The maximum number of auctions that can end in the same block.
+		#[pallet::constant]
+		type MaxAuctionsPerBlock: Get<u32>;
+		/// The maximum number of open offers on a single kitty.
+		#[pallet::constant]
+		type MaxOffersPerKitty: Get<u32>;
+		/// The maximum number of listings that can expire in the same block.
+		#[pallet::constant]
+		type MaxListingsExpiringPerBlock: Get<u32>;
+		/// The maximum number of rentals that can end in the same block.
+		#[pallet::constant]
+		type MaxRentalsEndingPerBlock: Get<u32>;
+		/// Blocks to wait after a mint commitment before its secret can be revealed. At least 1,
+		/// so the hash of the commitment block is known at reveal.
+		#[pallet::constant]
+		type RevealDelay: Get<Self::BlockNumber>;
+		/// Blocks after which an unrevealed mint commitment expires and its deposit is refunded,
+		/// less `CommitmentForfeit`. Must not exceed `frame_system::Config::BlockHashCount`.
+		#[pallet::constant]
+		type CommitmentTimeout: Get<Self::BlockNumber>;
+		/// The part of the deposit slashed when a mint commitment expires unrevealed, so that
+		/// letting an unwanted outcome lapse is not a free re-roll.
+		#[pallet::constant]
+		type CommitmentForfeit: Get<Perbill>;
+		/// The maximum number of mint commitments that can expire in the same block.
+		#[pallet::constant]
+		type MaxCommitmentsExpiringPerBlock: Get<u32>;
+		/// The maximum number of kitties in a single batch call.
+		#[pallet::constant]
+		type MaxBatch: Get<u32>;
+		/// Experience a kitty gains for winning a battle.
+		#[pallet::constant]
+		type ExperiencePerWin: Get<u32>;
+		/// Experience needed for each level.
+		#[pallet::constant]
+		type ExperiencePerLevel: Get<u32>;
+		/// The maximum number of accepted challenges fought out in the same block.
+		#[pallet::constant]
+		type MaxBattlesPerBlock: Get<u32>;
+		/// Maximum number of owners kept in the provenance log of a kitty.
+		#[pallet::constant]
+		type MaxProvenanceLength: Get<u32>;
+		/// Notified when a kitty is created, bred or minted.
+		type OnKittyCreated: OnKittyCreated<Self::AccountId>;
+		/// Notified when a kitty changes owner or is burned.
+		type OnKittyTransfer: OnKittyTransfer<Self::AccountId>;
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
 	}
 
 	#[pallet::storage]
@@ -55,30 +323,385 @@ pub mod pallet {
 
 	#[pallet::storage]
 	#[pallet::getter(fn kitties)]
-	pub type Kitties<T> = StorageMap<_, Blake2_128Concat, KittyId, Kitty>;
+	pub type Kitties<T: Config> = StorageMap<_, Blake2_128Concat, KittyId, KittyOf<T>>;
+
+	/// Number of living kitties.
+	#[pallet::storage]
+	#[pallet::getter(fn kitty_population)]
+	pub type KittyPopulation<T> = StorageValue<_, u32, ValueQuery>;
+
+	/// Number of living kitties with each variant of each trait, by trait index in
+	/// `TRAIT_SCHEMA` and variant.
+	#[pallet::storage]
+	#[pallet::getter(fn trait_count)]
+	pub type TraitCounts<T> =
+		StorageDoubleMap<_, Twox64Concat, u8, Twox64Concat, u8, u32, ValueQuery>;
+
+	/// The account that reserved the name deposit of a kitty, and the amount reserved.
+	#[pallet::storage]
+	#[pallet::getter(fn name_deposit)]
+	pub type NameDeposits<T: Config> =
+		StorageMap<_, Blake2_128Concat, KittyId, (T::AccountId, BalanceOf<T>)>;
 
 	#[pallet::storage]
 	#[pallet::getter(fn kitty_owner)]
 	pub type KittyOwner<T: Config> = StorageMap<_, Blake2_128Concat, KittyId, T::AccountId>;
 
+	/// Kitties owned by each account, kept in sync with `KittyOwner`.
+	#[pallet::storage]
+	#[pallet::getter(fn owned_kitties)]
+	pub type OwnedKitties<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		BoundedVec<KittyId, T::MaxKittiesOwned>,
+		ValueQuery,
+	>;
+
 	#[pallet::storage]
 	#[pallet::getter(fn kitty_parents)]
 	pub type KittyParents<T: Config> = StorageMap<_, Blake2_128Concat, KittyId, (KittyId, KittyId), OptionQuery>;
 
+	/// Generation of each kitty. Created kitties are generation 0, bred kitties are one more than
+	/// the older of their parents.
+	#[pallet::storage]
+	#[pallet::getter(fn kitty_generation)]
+	pub type KittyGeneration<T: Config> = StorageMap<_, Blake2_128Concat, KittyId, u32, ValueQuery>;
+
+	/// The account that created or bred each kitty, owed royalties on secondary sales.
+	#[pallet::storage]
+	#[pallet::getter(fn kitty_creator)]
+	pub type KittyCreator<T: Config> = StorageMap<_, Blake2_128Concat, KittyId, T::AccountId>;
+
+	/// The price paid to mint each kitty, the base of its burn refund. Genesis kitties and those
+	/// minted through `nonfungible::Mutate` paid nothing. Kitties minted before prices were
+	/// recorded have no entry and are refunded from the current `KittyPrice`.
+	#[pallet::storage]
+	#[pallet::getter(fn mint_price)]
+	pub type MintPrices<T: Config> = StorageMap<_, Blake2_128Concat, KittyId, BalanceOf<T>>;
+
+	/// The block in which a kitty was last used as a parent.
+	#[pallet::storage]
+	#[pallet::getter(fn last_bred)]
+	pub type LastBred<T: Config> = StorageMap<_, Blake2_128Concat, KittyId, T::BlockNumber>;
+
+	/// Kitties listed for sale, with the asking price set by the owner.
 	#[pallet::storage]
 	#[pallet::getter(fn kitty_on_sale)]
-	pub type KittyOnSale<T: Config> = StorageMap<_, Blake2_128Concat, KittyId, ()>;
+	pub type KittyOnSale<T: Config> = StorageMap<_, Blake2_128Concat, KittyId, BalanceOf<T>>;
+
+	/// The block at which a listing expires, for listings created with an expiry.
+	#[pallet::storage]
+	#[pallet::getter(fn sale_expiry)]
+	pub type SaleExpiry<T: Config> = StorageMap<_, Blake2_128Concat, KittyId, T::BlockNumber>;
+
+	/// Listings that expire at a given block, delisted in `on_initialize` of that block.
+	#[pallet::storage]
+	#[pallet::getter(fn sales_expiring)]
+	pub type SalesExpiring<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		T::BlockNumber,
+		BoundedVec<KittyId, T::MaxListingsExpiringPerBlock>,
+		ValueQuery,
+	>;
+
+	/// Running auctions by kitty.
+	#[pallet::storage]
+	#[pallet::getter(fn auctions)]
+	pub type Auctions<T: Config> = StorageMap<_, Blake2_128Concat, KittyId, AuctionOf<T>>;
+
+	/// Kitties whose auction ends at a given block, settled in `on_initialize` of that block.
+	#[pallet::storage]
+	#[pallet::getter(fn auctions_ending)]
+	pub type AuctionsEnding<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		T::BlockNumber,
+		BoundedVec<KittyId, T::MaxAuctionsPerBlock>,
+		ValueQuery,
+	>;
+
+	/// Open offers on each kitty.
+	#[pallet::storage]
+	#[pallet::getter(fn offers)]
+	pub type Offers<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		KittyId,
+		BoundedVec<OfferOf<T>, T::MaxOffersPerKitty>,
+		ValueQuery,
+	>;
+
+	/// The account approved to move a single kitty. Cleared whenever the kitty changes hands.
+	#[pallet::storage]
+	#[pallet::getter(fn kitty_approval)]
+	pub type KittyApprovals<T: Config> = StorageMap<_, Blake2_128Concat, KittyId, T::AccountId>;
+
+	/// Operators approved by an owner to move all of the owner's kitties.
+	#[pallet::storage]
+	#[pallet::getter(fn operator_approval)]
+	pub type OperatorApprovals<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Blake2_128Concat,
+		T::AccountId,
+		(),
+		OptionQuery,
+	>;
+
+	/// Stud fees of kitties offered as sires.
+	#[pallet::storage]
+	#[pallet::getter(fn siring_offer)]
+	pub type SiringOffers<T: Config> = StorageMap<_, Blake2_128Concat, KittyId, BalanceOf<T>>;
+
+	/// Rental terms offered by kitty owners.
+	#[pallet::storage]
+	#[pallet::getter(fn rental_offer)]
+	pub type RentalOffers<T: Config> = StorageMap<_, Blake2_128Concat, KittyId, RentalOfferOf<T>>;
+
+	/// Active rentals by kitty.
+	#[pallet::storage]
+	#[pallet::getter(fn rental)]
+	pub type Rentals<T: Config> = StorageMap<_, Blake2_128Concat, KittyId, RentalOf<T>>;
+
+	/// Kitties whose rental ends at a given block, returned in `on_initialize` of that block.
+	#[pallet::storage]
+	#[pallet::getter(fn rentals_ending)]
+	pub type RentalsEnding<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		T::BlockNumber,
+		BoundedVec<KittyId, T::MaxRentalsEndingPerBlock>,
+		ValueQuery,
+	>;
+
+	/// Open mint commitments by committer and the hash of their secret.
+	#[pallet::storage]
+	#[pallet::getter(fn commitment)]
+	pub type Commitments<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Blake2_128Concat,
+		T::Hash,
+		CommitmentOf<T>,
+	>;
+
+	/// Mint commitments that expire at a given block, refunded in `on_initialize` of that block.
+	#[pallet::storage]
+	#[pallet::getter(fn commitments_expiring)]
+	pub type CommitmentsExpiring<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		T::BlockNumber,
+		BoundedVec<(T::AccountId, T::Hash), T::MaxCommitmentsExpiringPerBlock>,
+		ValueQuery,
+	>;
+
+	/// Open challenges by the challenged kitty.
+	#[pallet::storage]
+	#[pallet::getter(fn challenge)]
+	pub type Challenges<T: Config> = StorageMap<_, Blake2_128Concat, KittyId, ChallengeOf<T>>;
+
+	/// The kitty challenged by each challenging kitty.
+	#[pallet::storage]
+	#[pallet::getter(fn challenged_kitty)]
+	pub type ChallengingKitties<T: Config> = StorageMap<_, Blake2_128Concat, KittyId, KittyId>;
+
+	/// Accepted challenges by the challenged kitty: the user who accepted and the block of the
+	/// acceptance, whose hash seeds the battle.
+	#[pallet::storage]
+	#[pallet::getter(fn accepted_challenge)]
+	pub type AcceptedChallenges<T: Config> =
+		StorageMap<_, Blake2_128Concat, KittyId, (T::AccountId, T::BlockNumber)>;
+
+	/// Challenged kitties whose battle is fought in `on_initialize` of a given block.
+	#[pallet::storage]
+	#[pallet::getter(fn battles_due)]
+	pub type BattlesDue<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		T::BlockNumber,
+		BoundedVec<KittyId, T::MaxBattlesPerBlock>,
+		ValueQuery,
+	>;
+
+	/// Battle record of each kitty that has fought.
+	#[pallet::storage]
+	#[pallet::getter(fn kitty_stats)]
+	pub type Stats<T: Config> = StorageMap<_, Blake2_128Concat, KittyId, KittyStats, ValueQuery>;
+
+	/// Owners of each kitty, oldest first. Only the latest `MaxProvenanceLength` are kept.
+	#[pallet::storage]
+	#[pallet::getter(fn provenance)]
+	pub type Provenance<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		KittyId,
+		BoundedVec<ProvenanceRecordOf<T>, T::MaxProvenanceLength>,
+		ValueQuery,
+	>;
+
+	/// Children bred from each kitty, the reverse of `KittyParents`.
+	#[pallet::storage]
+	pub type KittyChildren<T> =
+		StorageDoubleMap<_, Blake2_128Concat, KittyId, Blake2_128Concat, KittyId, (), OptionQuery>;
+
+	#[pallet::genesis_config]
+	pub struct GenesisConfig<T: Config> {
+		/// Kitties as `(owner, dna, name)`, given ids in order starting from 0.
+		pub kitties: Vec<(T::AccountId, [u8; 16], Vec<u8>)>,
+		/// The id of the next kitty created after genesis. Raised to the number of genesis
+		/// kitties if lower.
+		pub next_kitty_id: KittyId,
+	}
+
+	#[cfg(feature = "std")]
+	impl<T: Config> Default for GenesisConfig<T> {
+		fn default() -> Self {
+			Self { kitties: Vec::new(), next_kitty_id: 0 }
+		}
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
+		fn build(&self) {
+			// Genesis kitties are not paid for, so no price or name deposit is taken.
+			for (owner, dna, name) in self.kitties.iter() {
+				let name: BoundedVec<u8, T::MaxNameLength> =
+					name.clone().try_into().expect("genesis kitty name too long");
+				Pallet::<T>::insert_new_kitty(owner, *dna, name, None, Acquisition::Created, Zero::zero())
+					.expect("genesis account owns too many kitties");
+			}
+
+			NextKittyId::<T>::mutate(|next_id| *next_id = (*next_id).max(self.next_kitty_id));
+		}
+	}
 
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config> {
 		/// Event documentation should end with an array that provides descriptive names for event
 		/// parameters. [something, who]
-		KittyCreated { who: T::AccountId, kitty_id: KittyId, kitty: Kitty },
-		KittyBreed { who: T::AccountId, kitty_id: KittyId, kitty: Kitty },
+		KittyCreated { who: T::AccountId, kitty_id: KittyId, kitty: KittyOf<T> },
+		KittyBreed { who: T::AccountId, kitty_id: KittyId, kitty: KittyOf<T> },
+		KittyRenamed { who: T::AccountId, kitty_id: KittyId, name: BoundedVec<u8, T::MaxNameLength> },
 		KittyTransferred { from: T::AccountId, to: T::AccountId, kitty_id: KittyId },
-		KittyOnSale { who: T::AccountId, kitty_id: KittyId },
-		KittyBought { who: T::AccountId, kitty_id: KittyId },
+		KittyOnSale { who: T::AccountId, kitty_id: KittyId, price: BalanceOf<T> },
+		KittyPriceUpdated { who: T::AccountId, kitty_id: KittyId, price: BalanceOf<T> },
+		/// `fee` went to the marketplace and `royalty` to the creator, the rest to the seller.
+		KittyBought {
+			who: T::AccountId,
+			kitty_id: KittyId,
+			price: BalanceOf<T>,
+			fee: BalanceOf<T>,
+			royalty: BalanceOf<T>,
+		},
+		KittySaleCancelled { who: T::AccountId, kitty_id: KittyId },
+		KittySaleExpired { kitty_id: KittyId },
+		/// The listing was removed because the kitty changed hands outside of `buy`.
+		KittyDelisted { kitty_id: KittyId },
+		KittyBurned { who: T::AccountId, kitty_id: KittyId, refund: BalanceOf<T> },
+		AuctionCreated {
+			who: T::AccountId,
+			kitty_id: KittyId,
+			min_bid: BalanceOf<T>,
+			end_block: T::BlockNumber,
+		},
+		BidPlaced { who: T::AccountId, kitty_id: KittyId, amount: BalanceOf<T> },
+		/// The bid of `who` was outbid and `amount` has been unreserved.
+		BidReleased { who: T::AccountId, kitty_id: KittyId, amount: BalanceOf<T> },
+		AuctionSettled {
+			kitty_id: KittyId,
+			winner: T::AccountId,
+			price: BalanceOf<T>,
+			fee: BalanceOf<T>,
+			royalty: BalanceOf<T>,
+		},
+		/// The auction ended without a bid that could be settled. The kitty stays with the seller.
+		AuctionUnsold { kitty_id: KittyId },
+		OfferMade {
+			who: T::AccountId,
+			kitty_id: KittyId,
+			amount: BalanceOf<T>,
+			expiry: T::BlockNumber,
+		},
+		OfferWithdrawn { who: T::AccountId, kitty_id: KittyId, amount: BalanceOf<T> },
+		OfferAccepted {
+			who: T::AccountId,
+			offerer: T::AccountId,
+			kitty_id: KittyId,
+			amount: BalanceOf<T>,
+			fee: BalanceOf<T>,
+			royalty: BalanceOf<T>,
+		},
+		/// An expired offer was removed and its amount unreserved.
+		OfferExpired { who: T::AccountId, kitty_id: KittyId, amount: BalanceOf<T> },
+		/// `delegate` may move the kitty, or no one besides the owner when `None`.
+		Approval { owner: T::AccountId, delegate: Option<T::AccountId>, kitty_id: KittyId },
+		ApprovalForAll { owner: T::AccountId, operator: T::AccountId, approved: bool },
+		RentalOffered {
+			who: T::AccountId,
+			kitty_id: KittyId,
+			price_per_block: BalanceOf<T>,
+			max_duration: T::BlockNumber,
+		},
+		RentalOfferCancelled { who: T::AccountId, kitty_id: KittyId },
+		KittyRented {
+			who: T::AccountId,
+			kitty_id: KittyId,
+			end_block: T::BlockNumber,
+			cost: BalanceOf<T>,
+		},
+		RentalEnded { renter: T::AccountId, kitty_id: KittyId },
+		SiringOffered { who: T::AccountId, kitty_id: KittyId, fee: BalanceOf<T> },
+		SiringOfferCancelled { who: T::AccountId, kitty_id: KittyId },
+		/// `who` bred `kitty_id` with a sire of `sire_owner` and paid `fee` for it.
+		KittySired {
+			who: T::AccountId,
+			kitty_id: KittyId,
+			matron_id: KittyId,
+			sire_id: KittyId,
+			sire_owner: T::AccountId,
+			fee: BalanceOf<T>,
+		},
+		/// `who` committed to a mint that can be revealed from `reveal_at` until `expiry`.
+		MintCommitted {
+			who: T::AccountId,
+			commitment: T::Hash,
+			reveal_at: T::BlockNumber,
+			expiry: T::BlockNumber,
+		},
+		/// An unrevealed mint commitment expired. `forfeited` of its deposit was slashed and the
+		/// rest unreserved.
+		CommitmentExpired {
+			who: T::AccountId,
+			commitment: T::Hash,
+			deposit: BalanceOf<T>,
+			forfeited: BalanceOf<T>,
+		},
+		ChallengeIssued {
+			who: T::AccountId,
+			kitty_id: KittyId,
+			opponent: KittyId,
+			stake: BalanceOf<T>,
+		},
+		/// `who` accepted the challenge of `opponent` against `kitty_id` with the same stake. The
+		/// battle is fought at `battle_at`.
+		ChallengeAccepted {
+			who: T::AccountId,
+			kitty_id: KittyId,
+			opponent: KittyId,
+			battle_at: T::BlockNumber,
+		},
+		/// The challenge against `opponent` was withdrawn or declined, or its battle could not be
+		/// paid out, and the stakes were unreserved.
+		ChallengeCancelled { kitty_id: KittyId, opponent: KittyId },
+		/// `winner` beat `loser` and its user was `paid` out of the loser's stake. That is less
+		/// than the stake if the loser's reserve was slashed in the meantime.
+		BattleFought { winner: KittyId, loser: KittyId, paid: BalanceOf<T>, winner_level: u32 },
 	}
 
 	#[pallet::error]
@@ -89,83 +712,189 @@ pub mod pallet {
 		NotOnSale,
 		NoOwner,
 		AlreadyOnSale,
-		AlreadyOwned
+		AlreadyOwned,
+		/// The asking price is higher than the buyer is willing to pay.
+		PriceTooHigh,
+		/// The account already owns `MaxKittiesOwned` kitties.
+		TooManyKitties,
+		/// The kitty is being auctioned and can not be moved or listed.
+		KittyInAuction,
+		NotInAuction,
+		/// The auction end block must be in the future.
+		InvalidAuctionEnd,
+		/// Too many auctions already end in that block.
+		TooManyAuctions,
+		AuctionEnded,
+		/// The bid is below the minimum bid or not above the current best bid.
+		BidTooLow,
+		/// The offer expiry block must be in the future.
+		InvalidOfferExpiry,
+		/// The account already has an open offer on this kitty.
+		AlreadyOffered,
+		/// The kitty already has `MaxOffersPerKitty` open offers.
+		TooManyOffers,
+		NoOffer,
+		OfferExpired,
+		/// The listing expiry block must be in the future.
+		InvalidSaleExpiry,
+		/// Too many listings already expire in that block.
+		TooManyListingsExpiring,
+		/// The name is longer than `MaxNameLength`.
+		NameTooLong,
+		/// The caller is neither the owner nor approved to move the kitty.
+		NotApproved,
+		/// The kitty is rented out and can not change hands until the rental ends.
+		KittyRented,
+		NoRentalOffer,
+		/// The rental must last at least one block and at most the offered `max_duration`.
+		InvalidRentalDuration,
+		/// Too many rentals already end in that block.
+		TooManyRentals,
+		/// Only the renter can use a kitty while it is rented out.
+		NotKittyUser,
+		/// A parent is still resting from its last breeding.
+		BreedingCooldown,
+		/// The kitty is not offered as a sire.
+		NotForSiring,
+		/// The account already has an open commitment with this hash.
+		DuplicateCommitment,
+		/// There is no open commitment for the revealed secret.
+		NoCommitment,
+		/// `RevealDelay` has not passed since the commitment.
+		RevealTooEarly,
+		/// Too many commitments expire in the same block.
+		TooManyCommitments,
+		/// The kitty is in an open challenge.
+		KittyInBattle,
+		/// The kitty has not been challenged.
+		NoChallenge,
+		/// The challenge has been accepted and can no longer be cancelled.
+		ChallengeAlreadyAccepted,
+		/// Too many battles are already fought in that block.
+		TooManyBattles,
+		/// More items than `MaxBatch` in a batch call.
+		BatchTooLarge,
+		/// `T::Randomness` has no output yet. Use `commit_mint` instead.
+		RandomnessUnavailable,
+		/// The buyer's reserve no longer covers the price, for example after it was slashed.
+		InsufficientReserve,
 	}
 
 	#[pallet::hooks]
 	impl <T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
-		fn on_runtime_upgrade() -> Weight {
-			migrations::v1::migrate::<T>()
+		fn on_initialize(now: T::BlockNumber) -> Weight {
+			let ending = AuctionsEnding::<T>::take(now);
+			let count = ending.len() as u64;
+
+			for kitty_id in ending {
+				Self::settle_auction(kitty_id);
+			}
+
+			let expiring = SalesExpiring::<T>::take(now);
+			let expired = expiring.len() as u64;
+
+			for kitty_id in expiring {
+				SaleExpiry::<T>::remove(kitty_id);
+				KittyOnSale::<T>::remove(kitty_id);
+				Self::deposit_event(Event::KittySaleExpired { kitty_id });
+			}
+
+			let returning = RentalsEnding::<T>::take(now);
+			let returned = returning.len() as u64;
+
+			for kitty_id in returning {
+				if let Some(rental) = Rentals::<T>::take(kitty_id) {
+					Self::deposit_event(Event::RentalEnded { renter: rental.renter, kitty_id });
+				}
+			}
+
+			let fighting = BattlesDue::<T>::take(now);
+			let fought = fighting.len() as u64;
+
+			for kitty_id in fighting {
+				Self::fight_battle(kitty_id);
+			}
+
+			let lapsing = CommitmentsExpiring::<T>::take(now);
+			let lapsed = lapsing.len() as u64;
+
+			for (who, commitment) in lapsing {
+				if let Some(pending) = Commitments::<T>::take(&who, commitment) {
+					let forfeit = T::CommitmentForfeit::get() * pending.deposit;
+					let (_, unslashed) = T::Currency::slash_reserved(&who, forfeit);
+					T::Currency::unreserve(&who, pending.deposit.saturating_sub(forfeit));
+					Self::deposit_event(Event::CommitmentExpired {
+						who,
+						commitment,
+						deposit: pending.deposit,
+						forfeited: forfeit.saturating_sub(unslashed),
+					});
+				}
+			}
+
+			T::DbWeight::get()
+				.reads_writes(5 + returned + lapsed * 3, 5 + expired * 2 + returned + lapsed * 3)
+				.saturating_add(T::WeightInfo::settle_auction().saturating_mul(count))
+				.saturating_add(T::WeightInfo::fight_battle().saturating_mul(fought))
+		}
+
+		fn integrity_test() {
+			assert!(!T::RevealDelay::get().is_zero(), "`RevealDelay` must be at least one block");
+			assert!(
+				T::CommitmentTimeout::get() > T::RevealDelay::get(),
+				"`CommitmentTimeout` must be longer than `RevealDelay`"
+			);
+			assert!(
+				T::CommitmentTimeout::get() <= <T as frame_system::Config>::BlockHashCount::get(),
+				"`CommitmentTimeout` must not exceed `BlockHashCount`"
+			);
 		}
 	}
 
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
-		/// An example dispatchable that takes a singles value as a parameter, writes the value to
-		/// storage and emits an event. This function must be dispatched by a signed extrinsic.
+		/// Create a kitty with random dna. The dna comes from `T::Randomness`, which is public
+		/// before the call lands in a block; use `commit_mint` for dna nobody can know beforehand.
 		#[pallet::call_index(0)]
-		#[pallet::weight(10_000)]
-		pub fn create(origin: OriginFor<T>, name: [u8; 4]) -> DispatchResult {
+		#[pallet::weight(T::WeightInfo::create())]
+		pub fn create(origin: OriginFor<T>, name: Vec<u8>) -> DispatchResult {
 			let who = ensure_signed(origin)?;
 
-			let kitty_id = Self::get_next_id()?;
-			let dna = Self::random_value(&who);
-			let kitty = Kitty { dna, name };
+			let name: BoundedVec<u8, T::MaxNameLength> =
+				name.try_into().map_err(|_| Error::<T>::NameTooLong)?;
+			let dna = Self::random_value(&who)?;
 
-			let price = T::KittyPrice::get();
-			// T::Currency::reserve(&who, price)?;
-			T::Currency::transfer(&who, &Self::get_account_id(), price, ExistenceRequirement::KeepAlive)?;
+			Self::do_create(who, name, dna)?;
 
-			Kitties::<T>::insert(kitty_id, &kitty);
-			KittyOwner::<T>::insert(kitty_id, &who);
-			
-			Self::deposit_event(Event::KittyCreated { who, kitty_id, kitty });
-			
 			Ok(())
 		}
 
+		/// Breed two kitties the caller owns or rents. Which genes come from which parent is as
+		/// predictable as `create`'s dna.
 		#[pallet::call_index(1)]
-		#[pallet::weight(10_000)]
+		#[pallet::weight(T::WeightInfo::breed())]
 		pub fn breed(
 			origin: OriginFor<T>,
 			kitty_id_1: KittyId,
             kitty_id_2: KittyId,
-			name: [u8; 4],
+			name: Vec<u8>,
 		) -> DispatchResult {
 			let who = ensure_signed(origin)?;
 
 			ensure!(kitty_id_1 != kitty_id_2, Error::<T>::SameKittyId);
-			
-			ensure!(Kitties::<T>::contains_key(kitty_id_1), Error::<T>::InvalidKittyId);
-			ensure!(Kitties::<T>::contains_key(kitty_id_2), Error::<T>::InvalidKittyId);
-
-			let kitty_id = Self::get_next_id()?;
-			// let kitty_1 = Self::kitties(kitty_id_1).ok_or(Error::<T>::InvalidKittyId)?;
-			// let kitty_2 = Self::kitties(kitty_id_2).ok_or(Error::<T>::InvalidKittyId)?;
-
-			// let selector = Self::random_value(&who);
-            let dna = [0u8; 16];
-			// for i in 0..kitty_1.0.len() {
-			// 	// 0 选 kitty_2, 1 选 kitty_1
-			// 	data[i] = (kitty_1.0[i] & selector[i]) | (kitty_2.0[i] & !selector[i]);
-			// }
-			let kitty = Kitty{ dna, name };
-
-			let price = T::KittyPrice::get();
-			// T::Currency::reserve(&who, price)?;
-			T::Currency::transfer(&who, &Self::get_account_id(), price, ExistenceRequirement::KeepAlive)?;
+			Self::ensure_kitty_user(&who, kitty_id_1)?;
+			Self::ensure_kitty_user(&who, kitty_id_2)?;
 
-			Kitties::<T>::insert(kitty_id, &kitty);
-			KittyOwner::<T>::insert(kitty_id, &who);	
-			KittyParents::<T>::insert(kitty_id, (kitty_id_1, kitty_id_2));
+			let name: BoundedVec<u8, T::MaxNameLength> =
+				name.try_into().map_err(|_| Error::<T>::NameTooLong)?;
+			let selector = Self::random_value(&who)?;
+			Self::do_breed(who, kitty_id_1, kitty_id_2, name, selector)?;
 
-			Self::deposit_event(Event::KittyBreed { who, kitty_id, kitty });
-			
 			Ok(())
 		}
 
 		#[pallet::call_index(2)]
-		#[pallet::weight(10_000)]
+		#[pallet::weight(T::WeightInfo::transfer())]
 		pub fn transfer(
 			origin: OriginFor<T>,
             to: T::AccountId,
@@ -177,37 +906,28 @@ pub mod pallet {
 			let owner = Self::kitty_owner(kitty_id).ok_or(Error::<T>::InvalidKittyId)?;
 			ensure!(owner == who, Error::<T>::NotOwner);
 
-			KittyOwner::<T>::insert(kitty_id, &to);
-			
-			Self::deposit_event(Event::KittyTransferred { from: who, to, kitty_id });
-			
-			Ok(())
+			Self::do_transfer(who, to, kitty_id)
 		}
 
 		#[pallet::call_index(3)]
-		#[pallet::weight(10_000)]
+		#[pallet::weight(T::WeightInfo::sale())]
 		pub fn sale(
 			origin: OriginFor<T>,
 			kitty_id: KittyId,
+			price: BalanceOf<T>,
+			expiry: Option<T::BlockNumber>,
 		) -> DispatchResult {
 			let who = ensure_signed(origin)?;
 
-			Self::kitties(kitty_id).ok_or::<DispatchError>(Error::<T>::InvalidKittyId.into())?;
-
-			ensure!(Self::kitty_owner(kitty_id) == Some(who.clone()), Error::<T>::NotOwner);
-			ensure!(Self::kitty_on_sale(kitty_id).is_none(), Error::<T>::AlreadyOnSale);
-
-			<KittyOnSale<T>>::insert(kitty_id, ());
-			Self::deposit_event(Event::KittyOnSale { who, kitty_id });
-
-			Ok(())
+			Self::do_sale(who, kitty_id, price, expiry)
 		}
 
 		#[pallet::call_index(4)]
-        #[pallet::weight(10_000)]
+        #[pallet::weight(T::WeightInfo::buy())]
         pub fn buy(
             origin: OriginFor<T>,
             kitty_id: KittyId,
+			max_price: BalanceOf<T>,
 		) -> DispatchResult {
 			let who = ensure_signed(origin)?;
 
@@ -215,41 +935,1359 @@ pub mod pallet {
 
 			let owner = Self::kitty_owner(kitty_id).ok_or::<DispatchError>(Error::<T>::NoOwner.into())?;
 			ensure!(owner != who, Error::<T>::AlreadyOwned);
-			ensure!(Self::kitty_on_sale(kitty_id).is_some(), Error::<T>::NotOnSale);
+			ensure!(!Rentals::<T>::contains_key(kitty_id), Error::<T>::KittyRented);
+			ensure!(!Self::in_battle(kitty_id), Error::<T>::KittyInBattle);
+			let price = Self::kitty_on_sale(kitty_id).ok_or(Error::<T>::NotOnSale)?;
+			ensure!(price <= max_price, Error::<T>::PriceTooHigh);
 
-			let price = T::KittyPrice::get();
 			// T::Currency::reserve(&who, price)?;
 			// T::Currency::unreserve(&owner, price);
-			T::Currency::transfer(&who, &owner, price, ExistenceRequirement::KeepAlive)?;
+			let (fee, royalty) = Self::pay_for_kitty(&who, &owner, kitty_id, price, false)?;
 
-			<KittyOwner<T>>::insert(kitty_id, &who);
-			<KittyOnSale<T>>::remove(kitty_id);
+			Self::move_kitty(&owner, &who, kitty_id, Acquisition::Bought)?;
+			Self::remove_listing(kitty_id);
 
-			Self::deposit_event(Event::KittyBought { who, kitty_id });
+			Self::deposit_event(Event::KittyBought { who, kitty_id, price, fee, royalty });
 
 			Ok(())
 		}
-	}
 
-	impl<T: Config> Pallet<T> {
-		fn get_next_id() -> Result<KittyId, DispatchError> {
-			NextKittyId::<T>::try_mutate(|next_id| -> Result<KittyId, DispatchError> {
-				let current_id = *next_id;
-				*next_id = next_id.checked_add(1).ok_or::<DispatchError>(Error::<T>::InvalidKittyId.into())?;
-				Ok(current_id)
-			})
+		#[pallet::call_index(5)]
+		#[pallet::weight(T::WeightInfo::update_price())]
+		pub fn update_price(
+			origin: OriginFor<T>,
+			kitty_id: KittyId,
+			price: BalanceOf<T>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			Self::kitties(kitty_id).ok_or::<DispatchError>(Error::<T>::InvalidKittyId.into())?;
+
+			ensure!(Self::kitty_owner(kitty_id) == Some(who.clone()), Error::<T>::NotOwner);
+			ensure!(Self::kitty_on_sale(kitty_id).is_some(), Error::<T>::NotOnSale);
+
+			<KittyOnSale<T>>::insert(kitty_id, price);
+			Self::deposit_event(Event::KittyPriceUpdated { who, kitty_id, price });
+
+			Ok(())
 		}
 
-		fn random_value(sender: &T::AccountId) -> [u8; 16] {
-			let payload = (
-				T::Randomness::random_seed(),
-				&sender,
-				<frame_system::Pallet<T>>::extrinsic_index(),
+		/// Put a kitty up for auction until `end_block`. The kitty can not be transferred or
+		/// listed for sale while the auction runs.
+		#[pallet::call_index(6)]
+		#[pallet::weight(T::WeightInfo::create_auction())]
+		pub fn create_auction(
+			origin: OriginFor<T>,
+			kitty_id: KittyId,
+			min_bid: BalanceOf<T>,
+			end_block: T::BlockNumber,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			Self::kitties(kitty_id).ok_or::<DispatchError>(Error::<T>::InvalidKittyId.into())?;
+
+			ensure!(Self::kitty_owner(kitty_id) == Some(who.clone()), Error::<T>::NotOwner);
+			ensure!(Self::kitty_on_sale(kitty_id).is_none(), Error::<T>::AlreadyOnSale);
+			ensure!(!Auctions::<T>::contains_key(kitty_id), Error::<T>::KittyInAuction);
+			ensure!(!Rentals::<T>::contains_key(kitty_id), Error::<T>::KittyRented);
+			ensure!(!Self::in_battle(kitty_id), Error::<T>::KittyInBattle);
+			ensure!(
+				end_block > <frame_system::Pallet<T>>::block_number(),
+				Error::<T>::InvalidAuctionEnd
+			);
+
+			AuctionsEnding::<T>::try_mutate(end_block, |ending| ending.try_push(kitty_id))
+				.map_err(|_| Error::<T>::TooManyAuctions)?;
+			Auctions::<T>::insert(
+				kitty_id,
+				Auction { seller: who.clone(), min_bid, end_block, best_bid: None },
+			);
+
+			Self::deposit_event(Event::AuctionCreated { who, kitty_id, min_bid, end_block });
+
+			Ok(())
+		}
+
+		/// Bid on a running auction. The bid is reserved and the previous best bid is
+		/// unreserved.
+		#[pallet::call_index(7)]
+		#[pallet::weight(T::WeightInfo::bid())]
+		pub fn bid(
+			origin: OriginFor<T>,
+			kitty_id: KittyId,
+			amount: BalanceOf<T>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let mut auction = Self::auctions(kitty_id).ok_or(Error::<T>::NotInAuction)?;
+			ensure!(
+				<frame_system::Pallet<T>>::block_number() < auction.end_block,
+				Error::<T>::AuctionEnded
+			);
+			ensure!(auction.seller != who, Error::<T>::AlreadyOwned);
+			ensure!(amount >= auction.min_bid, Error::<T>::BidTooLow);
+
+			match auction.best_bid.take() {
+				Some((best_bidder, best_amount)) => {
+					ensure!(amount > best_amount, Error::<T>::BidTooLow);
+					if best_bidder == who {
+						T::Currency::reserve(&who, amount - best_amount)?;
+					} else {
+						T::Currency::reserve(&who, amount)?;
+						T::Currency::unreserve(&best_bidder, best_amount);
+						Self::deposit_event(Event::BidReleased {
+							who: best_bidder,
+							kitty_id,
+							amount: best_amount,
+						});
+					}
+				},
+				None => T::Currency::reserve(&who, amount)?,
+			}
+
+			auction.best_bid = Some((who.clone(), amount));
+			Auctions::<T>::insert(kitty_id, auction);
+
+			Self::deposit_event(Event::BidPlaced { who, kitty_id, amount });
+
+			Ok(())
+		}
+
+		/// Offer to buy a kitty, listed or not, for `amount` until `expiry`. The amount is
+		/// reserved until the offer is withdrawn, accepted or expired.
+		#[pallet::call_index(8)]
+		#[pallet::weight(T::WeightInfo::make_offer())]
+		pub fn make_offer(
+			origin: OriginFor<T>,
+			kitty_id: KittyId,
+			amount: BalanceOf<T>,
+			expiry: T::BlockNumber,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let owner = Self::kitty_owner(kitty_id).ok_or(Error::<T>::InvalidKittyId)?;
+			ensure!(owner != who, Error::<T>::AlreadyOwned);
+			ensure!(
+				expiry > <frame_system::Pallet<T>>::block_number(),
+				Error::<T>::InvalidOfferExpiry
+			);
+
+			let mut offers = Self::purge_expired_offers(kitty_id);
+			ensure!(!offers.iter().any(|offer| offer.offerer == who), Error::<T>::AlreadyOffered);
+			offers
+				.try_push(Offer { offerer: who.clone(), amount, expiry })
+				.map_err(|_| Error::<T>::TooManyOffers)?;
+
+			T::Currency::reserve(&who, amount)?;
+			Offers::<T>::insert(kitty_id, offers);
+
+			Self::deposit_event(Event::OfferMade { who, kitty_id, amount, expiry });
+
+			Ok(())
+		}
+
+		/// Withdraw an offer, expired or not, and unreserve its amount. Expired offers of others
+		/// on the kitty are released too.
+		#[pallet::call_index(9)]
+		#[pallet::weight(T::WeightInfo::withdraw_offer())]
+		pub fn withdraw_offer(origin: OriginFor<T>, kitty_id: KittyId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let offer = Self::take_offer(kitty_id, &who).ok_or(Error::<T>::NoOffer)?;
+			T::Currency::unreserve(&who, offer.amount);
+			Self::purge_expired_offers(kitty_id);
+
+			Self::deposit_event(Event::OfferWithdrawn { who, kitty_id, amount: offer.amount });
+
+			Ok(())
+		}
+
+		/// Sell a kitty to `offerer` for the amount they offered. Expired offers on the kitty are
+		/// released.
+		#[pallet::call_index(10)]
+		#[pallet::weight(T::WeightInfo::accept_offer())]
+		pub fn accept_offer(
+			origin: OriginFor<T>,
+			kitty_id: KittyId,
+			offerer: T::AccountId,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			ensure!(Self::kitty_owner(kitty_id) == Some(who.clone()), Error::<T>::NotOwner);
+			ensure!(!Auctions::<T>::contains_key(kitty_id), Error::<T>::KittyInAuction);
+			ensure!(!Rentals::<T>::contains_key(kitty_id), Error::<T>::KittyRented);
+			ensure!(!Self::in_battle(kitty_id), Error::<T>::KittyInBattle);
+
+			let offer = Self::take_offer(kitty_id, &offerer).ok_or(Error::<T>::NoOffer)?;
+			ensure!(
+				offer.expiry > <frame_system::Pallet<T>>::block_number(),
+				Error::<T>::OfferExpired
 			);
-			payload.using_encoded(blake2_128)
+
+			Self::move_kitty(&who, &offerer, kitty_id, Acquisition::Bought)?;
+			if Self::remove_listing(kitty_id) {
+				Self::deposit_event(Event::KittyDelisted { kitty_id });
+			}
+			let (fee, royalty) = Self::pay_for_kitty(&offerer, &who, kitty_id, offer.amount, true)?;
+			Self::purge_expired_offers(kitty_id);
+
+			Self::deposit_event(Event::OfferAccepted {
+				who,
+				offerer,
+				kitty_id,
+				amount: offer.amount,
+				fee,
+				royalty,
+			});
+
+			Ok(())
+		}
+
+		/// Take a listed kitty off the market.
+		#[pallet::call_index(11)]
+		#[pallet::weight(T::WeightInfo::cancel_sale())]
+		pub fn cancel_sale(origin: OriginFor<T>, kitty_id: KittyId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			ensure!(Self::kitty_owner(kitty_id) == Some(who.clone()), Error::<T>::NotOwner);
+			ensure!(Self::remove_listing(kitty_id), Error::<T>::NotOnSale);
+
+			Self::deposit_event(Event::KittySaleCancelled { who, kitty_id });
+
+			Ok(())
+		}
+
+		/// Destroy a kitty and refund `BurnRefund` of the price paid to mint it from the pallet
+		/// account.
+		/// Open offers are released. The kitty stays in the family tree while it has children.
+		#[pallet::call_index(12)]
+		#[pallet::weight(T::WeightInfo::burn())]
+		pub fn burn(origin: OriginFor<T>, kitty_id: KittyId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			ensure!(Self::kitty_owner(kitty_id) == Some(who.clone()), Error::<T>::NotOwner);
+			ensure!(!Auctions::<T>::contains_key(kitty_id), Error::<T>::KittyInAuction);
+			ensure!(!Rentals::<T>::contains_key(kitty_id), Error::<T>::KittyRented);
+			ensure!(!Self::in_battle(kitty_id), Error::<T>::KittyInBattle);
+
+			let paid = Self::mint_price(kitty_id).unwrap_or_else(T::KittyPrice::get);
+			let refund = T::BurnRefund::get() * paid;
+			T::Currency::transfer(
+				&Self::get_account_id(),
+				&who,
+				refund,
+				ExistenceRequirement::AllowDeath,
+			)?;
+
+			Self::do_burn(who, kitty_id, refund);
+
+			Ok(())
+		}
+
+		/// Rename a kitty. The name deposit is released to whoever paid it and the deposit for
+		/// the new name is reserved from the owner.
+		#[pallet::call_index(13)]
+		#[pallet::weight(T::WeightInfo::rename())]
+		pub fn rename(origin: OriginFor<T>, kitty_id: KittyId, name: Vec<u8>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let name: BoundedVec<u8, T::MaxNameLength> =
+				name.try_into().map_err(|_| Error::<T>::NameTooLong)?;
+			let owner = Self::kitty_owner(kitty_id).ok_or(Error::<T>::InvalidKittyId)?;
+			ensure!(owner == who, Error::<T>::NotOwner);
+
+			Self::do_rename(who, kitty_id, name)
+		}
+
+		/// Approve `delegate` to move a kitty on behalf of its owner, or clear the approval with
+		/// `None`. Callable by the owner or one of the owner's operators.
+		#[pallet::call_index(14)]
+		#[pallet::weight(T::WeightInfo::approve())]
+		pub fn approve(
+			origin: OriginFor<T>,
+			kitty_id: KittyId,
+			delegate: Option<T::AccountId>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let owner = Self::kitty_owner(kitty_id).ok_or(Error::<T>::InvalidKittyId)?;
+			ensure!(
+				owner == who || OperatorApprovals::<T>::contains_key(&owner, &who),
+				Error::<T>::NotApproved
+			);
+
+			match &delegate {
+				Some(delegate) => KittyApprovals::<T>::insert(kitty_id, delegate),
+				None => KittyApprovals::<T>::remove(kitty_id),
+			}
+
+			Self::deposit_event(Event::Approval { owner, delegate, kitty_id });
+
+			Ok(())
+		}
+
+		/// Allow or disallow `operator` to move every kitty of the caller.
+		#[pallet::call_index(15)]
+		#[pallet::weight(T::WeightInfo::set_approval_for_all())]
+		pub fn set_approval_for_all(
+			origin: OriginFor<T>,
+			operator: T::AccountId,
+			approved: bool,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			if approved {
+				OperatorApprovals::<T>::insert(&who, &operator, ());
+			} else {
+				OperatorApprovals::<T>::remove(&who, &operator);
+			}
+
+			Self::deposit_event(Event::ApprovalForAll { owner: who, operator, approved });
+
+			Ok(())
+		}
+
+		/// Move a kitty from `from` to `to`. The caller must be `from`, the approved delegate of
+		/// the kitty or an operator of `from`.
+		#[pallet::call_index(16)]
+		#[pallet::weight(T::WeightInfo::transfer_from())]
+		pub fn transfer_from(
+			origin: OriginFor<T>,
+			from: T::AccountId,
+			to: T::AccountId,
+			kitty_id: KittyId,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let owner = Self::kitty_owner(kitty_id).ok_or(Error::<T>::InvalidKittyId)?;
+			ensure!(owner == from, Error::<T>::NotOwner);
+			ensure!(Self::is_approved(&who, &owner, kitty_id), Error::<T>::NotApproved);
+
+			Self::do_transfer(from, to, kitty_id)
+		}
+
+		/// Offer a kitty for rent at `price_per_block` for at most `max_duration` blocks. Replaces
+		/// any previous terms; a running rental is not affected.
+		#[pallet::call_index(17)]
+		#[pallet::weight(T::WeightInfo::offer_rental())]
+		pub fn offer_rental(
+			origin: OriginFor<T>,
+			kitty_id: KittyId,
+			price_per_block: BalanceOf<T>,
+			max_duration: T::BlockNumber,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			ensure!(Self::kitty_owner(kitty_id) == Some(who.clone()), Error::<T>::NotOwner);
+			ensure!(!Auctions::<T>::contains_key(kitty_id), Error::<T>::KittyInAuction);
+			ensure!(!Self::in_battle(kitty_id), Error::<T>::KittyInBattle);
+			ensure!(!max_duration.is_zero(), Error::<T>::InvalidRentalDuration);
+
+			RentalOffers::<T>::insert(kitty_id, RentalOffer { price_per_block, max_duration });
+			Self::deposit_event(Event::RentalOffered { who, kitty_id, price_per_block, max_duration });
+
+			Ok(())
+		}
+
+		#[pallet::call_index(18)]
+		#[pallet::weight(T::WeightInfo::cancel_rental_offer())]
+		pub fn cancel_rental_offer(origin: OriginFor<T>, kitty_id: KittyId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			ensure!(Self::kitty_owner(kitty_id) == Some(who.clone()), Error::<T>::NotOwner);
+			RentalOffers::<T>::take(kitty_id).ok_or(Error::<T>::NoRentalOffer)?;
+
+			Self::deposit_event(Event::RentalOfferCancelled { who, kitty_id });
+
+			Ok(())
+		}
+
+		/// Rent a kitty for `blocks` blocks. The whole rent is paid to the owner up front. Kitties
+		/// listed for sale can not be rented.
+		#[pallet::call_index(19)]
+		#[pallet::weight(T::WeightInfo::rent())]
+		pub fn rent(origin: OriginFor<T>, kitty_id: KittyId, blocks: T::BlockNumber) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let owner = Self::kitty_owner(kitty_id).ok_or(Error::<T>::InvalidKittyId)?;
+			ensure!(owner != who, Error::<T>::AlreadyOwned);
+			let offer = Self::rental_offer(kitty_id).ok_or(Error::<T>::NoRentalOffer)?;
+			ensure!(!Rentals::<T>::contains_key(kitty_id), Error::<T>::KittyRented);
+			ensure!(!Auctions::<T>::contains_key(kitty_id), Error::<T>::KittyInAuction);
+			ensure!(Self::kitty_on_sale(kitty_id).is_none(), Error::<T>::AlreadyOnSale);
+			ensure!(!Self::in_battle(kitty_id), Error::<T>::KittyInBattle);
+			ensure!(
+				!blocks.is_zero() && blocks <= offer.max_duration,
+				Error::<T>::InvalidRentalDuration
+			);
+
+			let blocks_u32: u32 = blocks.unique_saturated_into();
+			let cost = offer.price_per_block.saturating_mul(blocks_u32.into());
+			T::Currency::transfer(&who, &owner, cost, ExistenceRequirement::KeepAlive)?;
+
+			let end_block = <frame_system::Pallet<T>>::block_number().saturating_add(blocks);
+			RentalsEnding::<T>::try_mutate(end_block, |ending| ending.try_push(kitty_id))
+				.map_err(|_| Error::<T>::TooManyRentals)?;
+			Rentals::<T>::insert(kitty_id, Rental { renter: who.clone(), end_block });
+
+			Self::deposit_event(Event::KittyRented { who, kitty_id, end_block, cost });
+
+			Ok(())
+		}
+
+		/// Let others breed with a kitty as the sire for `fee`, paid to the owner on each breeding.
+		#[pallet::call_index(20)]
+		#[pallet::weight(T::WeightInfo::offer_siring())]
+		pub fn offer_siring(origin: OriginFor<T>, kitty_id: KittyId, fee: BalanceOf<T>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			ensure!(Self::kitty_owner(kitty_id) == Some(who.clone()), Error::<T>::NotOwner);
+
+			SiringOffers::<T>::insert(kitty_id, fee);
+			Self::deposit_event(Event::SiringOffered { who, kitty_id, fee });
+
+			Ok(())
+		}
+
+		#[pallet::call_index(21)]
+		#[pallet::weight(T::WeightInfo::cancel_siring_offer())]
+		pub fn cancel_siring_offer(origin: OriginFor<T>, kitty_id: KittyId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			ensure!(Self::kitty_owner(kitty_id) == Some(who.clone()), Error::<T>::NotOwner);
+			SiringOffers::<T>::take(kitty_id).ok_or(Error::<T>::NotForSiring)?;
+
+			Self::deposit_event(Event::SiringOfferCancelled { who, kitty_id });
+
+			Ok(())
+		}
+
+		/// Breed the caller's `matron_id` with a sire offered by someone else, paying the stud
+		/// fee to the sire owner. Fails if the fee is above `max_fee`.
+		#[pallet::call_index(22)]
+		#[pallet::weight(T::WeightInfo::breed_with_sire())]
+		pub fn breed_with_sire(
+			origin: OriginFor<T>,
+			matron_id: KittyId,
+			sire_id: KittyId,
+			max_fee: BalanceOf<T>,
+			name: Vec<u8>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			ensure!(matron_id != sire_id, Error::<T>::SameKittyId);
+			Self::ensure_kitty_user(&who, matron_id)?;
+			let fee = Self::siring_offer(sire_id).ok_or(Error::<T>::NotForSiring)?;
+			ensure!(fee <= max_fee, Error::<T>::PriceTooHigh);
+			ensure!(!Rentals::<T>::contains_key(sire_id), Error::<T>::KittyRented);
+			let sire_owner = Self::kitty_owner(sire_id).ok_or(Error::<T>::InvalidKittyId)?;
+
+			let name: BoundedVec<u8, T::MaxNameLength> =
+				name.try_into().map_err(|_| Error::<T>::NameTooLong)?;
+			let selector = Self::random_value(&who)?;
+			let kitty_id = Self::do_breed(who.clone(), matron_id, sire_id, name, selector)?;
+			T::Currency::transfer(&who, &sire_owner, fee, ExistenceRequirement::KeepAlive)?;
+
+			Self::deposit_event(Event::KittySired { who, kitty_id, matron_id, sire_id, sire_owner, fee });
+
+			Ok(())
+		}
+
+		/// Commit to minting a kitty, created or bred from `parents`, whose DNA comes from a
+		/// secret hashing to `commitment`. `KittyPrice` is reserved as a deposit until the secret
+		/// is revealed with `reveal_mint`. If it is not revealed within `CommitmentTimeout`, the
+		/// deposit is refunded less `CommitmentForfeit`.
+		///
+		/// This is the two-phase alternative to `create` and `breed`, which stay one-step and
+		/// keep drawing their DNA from `T::Randomness`.
+		#[pallet::call_index(23)]
+		#[pallet::weight(T::WeightInfo::commit_mint())]
+		pub fn commit_mint(
+			origin: OriginFor<T>,
+			commitment: T::Hash,
+			parents: Option<(KittyId, KittyId)>,
+			name: Vec<u8>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let name: BoundedVec<u8, T::MaxNameLength> =
+				name.try_into().map_err(|_| Error::<T>::NameTooLong)?;
+			if let Some((kitty_id_1, kitty_id_2)) = parents {
+				ensure!(kitty_id_1 != kitty_id_2, Error::<T>::SameKittyId);
+				Self::ensure_kitty_user(&who, kitty_id_1)?;
+				Self::ensure_kitty_user(&who, kitty_id_2)?;
+			}
+			ensure!(
+				!Commitments::<T>::contains_key(&who, commitment),
+				Error::<T>::DuplicateCommitment
+			);
+
+			let now = <frame_system::Pallet<T>>::block_number();
+			let expiry = now.saturating_add(T::CommitmentTimeout::get());
+			CommitmentsExpiring::<T>::try_mutate(expiry, |pending| {
+				pending.try_push((who.clone(), commitment))
+			})
+			.map_err(|_| Error::<T>::TooManyCommitments)?;
+
+			let deposit = T::KittyPrice::get();
+			T::Currency::reserve(&who, deposit)?;
+			Commitments::<T>::insert(&who, commitment, Commitment { parents, name, block: now, deposit });
+
+			let reveal_at = now.saturating_add(T::RevealDelay::get());
+			Self::deposit_event(Event::MintCommitted { who, commitment, reveal_at, expiry });
+
+			Ok(())
+		}
+
+		/// Reveal the secret of a mint commitment and mint the kitty. The DNA mixes the secret
+		/// with the hash of the block the commitment was made in, which nobody knew when
+		/// committing. The deposit is released and `KittyPrice` charged as usual.
+		#[pallet::call_index(24)]
+		#[pallet::weight(T::WeightInfo::reveal_mint())]
+		pub fn reveal_mint(origin: OriginFor<T>, secret: [u8; 32]) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let commitment = T::Hashing::hash(&secret);
+			let pending = Self::commitment(&who, commitment).ok_or(Error::<T>::NoCommitment)?;
+			let now = <frame_system::Pallet<T>>::block_number();
+			ensure!(
+				now >= pending.block.saturating_add(T::RevealDelay::get()),
+				Error::<T>::RevealTooEarly
+			);
+
+			Commitments::<T>::remove(&who, commitment);
+			let expiry = pending.block.saturating_add(T::CommitmentTimeout::get());
+			CommitmentsExpiring::<T>::mutate(expiry, |lapsing| {
+				lapsing.retain(|(committer, hash)| *committer != who || *hash != commitment)
+			});
+			T::Currency::unreserve(&who, pending.deposit);
+
+			let block_hash = <frame_system::Pallet<T>>::block_hash(pending.block);
+			let random = (secret, block_hash).using_encoded(blake2_128);
+
+			match pending.parents {
+				None => {
+					Self::do_create(who, pending.name, random)?;
+				},
+				Some((kitty_id_1, kitty_id_2)) => {
+					// The parents may have changed hands since the commitment.
+					Self::ensure_kitty_user(&who, kitty_id_1)?;
+					Self::ensure_kitty_user(&who, kitty_id_2)?;
+					Self::do_breed(who, kitty_id_1, kitty_id_2, pending.name, random)?;
+				},
+			}
+
+			Ok(())
+		}
+
+		/// Challenge `opponent` with the caller's `kitty_id`, reserving `stake`. The user of the
+		/// opponent can accept with the same stake; the winner takes both.
+		#[pallet::call_index(25)]
+		#[pallet::weight(T::WeightInfo::challenge())]
+		pub fn challenge(
+			origin: OriginFor<T>,
+			kitty_id: KittyId,
+			opponent: KittyId,
+			stake: BalanceOf<T>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			ensure!(kitty_id != opponent, Error::<T>::SameKittyId);
+			Self::ensure_kitty_user(&who, kitty_id)?;
+			let opponent_user = Self::kitty_user(opponent).ok_or(Error::<T>::InvalidKittyId)?;
+			ensure!(opponent_user != who, Error::<T>::AlreadyOwned);
+			for id in [kitty_id, opponent] {
+				ensure!(!Self::in_battle(id), Error::<T>::KittyInBattle);
+				ensure!(!Auctions::<T>::contains_key(id), Error::<T>::KittyInAuction);
+			}
+
+			T::Currency::reserve(&who, stake)?;
+			Challenges::<T>::insert(opponent, Challenge { challenger: who.clone(), kitty_id, stake });
+			ChallengingKitties::<T>::insert(kitty_id, opponent);
+
+			Self::deposit_event(Event::ChallengeIssued { who, kitty_id, opponent, stake });
+
+			Ok(())
+		}
+
+		/// Accept the challenge against `kitty_id`, reserving the same stake. The battle is
+		/// fought in the next block, seeded with the hash of this one, so neither side can know
+		/// the outcome when committing to it. The outcome is drawn from the battle power of both
+		/// kitties; the winner gains experience and its user receives the stake of the loser.
+		#[pallet::call_index(26)]
+		#[pallet::weight(T::WeightInfo::accept_challenge())]
+		pub fn accept_challenge(origin: OriginFor<T>, kitty_id: KittyId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			Self::ensure_kitty_user(&who, kitty_id)?;
+			let challenge = Self::challenge(kitty_id).ok_or(Error::<T>::NoChallenge)?;
+			ensure!(
+				!AcceptedChallenges::<T>::contains_key(kitty_id),
+				Error::<T>::ChallengeAlreadyAccepted
+			);
+
+			let now = <frame_system::Pallet<T>>::block_number();
+			let battle_at = now.saturating_add(One::one());
+			BattlesDue::<T>::try_mutate(battle_at, |due| due.try_push(kitty_id))
+				.map_err(|_| Error::<T>::TooManyBattles)?;
+			T::Currency::reserve(&who, challenge.stake)?;
+			AcceptedChallenges::<T>::insert(kitty_id, (who.clone(), now));
+
+			Self::deposit_event(Event::ChallengeAccepted {
+				who,
+				kitty_id,
+				opponent: challenge.kitty_id,
+				battle_at,
+			});
+
+			Ok(())
+		}
+
+		/// Withdraw a challenge as the challenger, or decline it as the user of the challenged
+		/// `kitty_id`. The stake is unreserved. An accepted challenge can not be cancelled.
+		#[pallet::call_index(27)]
+		#[pallet::weight(T::WeightInfo::cancel_challenge())]
+		pub fn cancel_challenge(origin: OriginFor<T>, kitty_id: KittyId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let challenge = Self::challenge(kitty_id).ok_or(Error::<T>::NoChallenge)?;
+			ensure!(
+				challenge.challenger == who || Self::kitty_user(kitty_id) == Some(who),
+				Error::<T>::NotOwner
+			);
+			ensure!(
+				!AcceptedChallenges::<T>::contains_key(kitty_id),
+				Error::<T>::ChallengeAlreadyAccepted
+			);
+
+			Challenges::<T>::remove(kitty_id);
+			ChallengingKitties::<T>::remove(challenge.kitty_id);
+			T::Currency::unreserve(&challenge.challenger, challenge.stake);
+
+			Self::deposit_event(Event::ChallengeCancelled {
+				kitty_id: challenge.kitty_id,
+				opponent: kitty_id,
+			});
+
+			Ok(())
+		}
+
+		/// Transfer several kitties of the caller at once. Either every transfer succeeds or
+		/// none is made.
+		#[pallet::call_index(28)]
+		#[pallet::weight(T::WeightInfo::batch_transfer(transfers.len() as u32))]
+		pub fn batch_transfer(
+			origin: OriginFor<T>,
+			transfers: Vec<(T::AccountId, KittyId)>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			ensure!(transfers.len() as u32 <= T::MaxBatch::get(), Error::<T>::BatchTooLarge);
+
+			for (to, kitty_id) in transfers {
+				let owner = Self::kitty_owner(kitty_id).ok_or(Error::<T>::InvalidKittyId)?;
+				ensure!(owner == who, Error::<T>::NotOwner);
+				Self::do_transfer(who.clone(), to, kitty_id)?;
+			}
+
+			Ok(())
+		}
+
+		/// List several kitties of the caller for sale at once, without expiry. Either every
+		/// kitty is listed or none is.
+		#[pallet::call_index(29)]
+		#[pallet::weight(T::WeightInfo::batch_sale(listings.len() as u32))]
+		pub fn batch_sale(
+			origin: OriginFor<T>,
+			listings: Vec<(KittyId, BalanceOf<T>)>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			ensure!(listings.len() as u32 <= T::MaxBatch::get(), Error::<T>::BatchTooLarge);
+
+			for (kitty_id, price) in listings {
+				Self::do_sale(who.clone(), kitty_id, price, None)?;
+			}
+
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		fn get_next_id() -> Result<KittyId, DispatchError> {
+			NextKittyId::<T>::try_mutate(|next_id| -> Result<KittyId, DispatchError> {
+				let current_id = *next_id;
+				*next_id = next_id.checked_add(1).ok_or::<DispatchError>(Error::<T>::InvalidKittyId.into())?;
+				Ok(current_id)
+			})
+		}
+
+		/// Randomness for a one-step mint or breed of `sender`. Only as unpredictable as
+		/// `T::Randomness`: with BABE anyone can compute it from the parent block. Fails while
+		/// the source has no output, rather than falling back to values the caller controls.
+		pub(crate) fn random_value(sender: &T::AccountId) -> Result<[u8; 16], DispatchError> {
+			let (seed, _) = T::Randomness::random_seed();
+			let seed = seed.ok_or(Error::<T>::RandomnessUnavailable)?;
+			let payload = (seed, &sender, <frame_system::Pallet<T>>::extrinsic_index());
+			Ok(payload.using_encoded(blake2_128))
+		}
+
+		/// Take each bit of the child DNA from `dna_1` where `selector` is set and from `dna_2`
+		/// otherwise.
+		pub fn mix_dna(dna_1: &[u8; 16], dna_2: &[u8; 16], selector: &[u8; 16]) -> [u8; 16] {
+			let mut dna = [0u8; 16];
+			for (i, gene) in dna.iter_mut().enumerate() {
+				*gene = (dna_1[i] & selector[i]) | (dna_2[i] & !selector[i]);
+			}
+			dna
+		}
+
+		/// Reserve the deposit for `name` from `who` and record it against the kitty.
+		fn reserve_name_deposit(
+			who: &T::AccountId,
+			kitty_id: KittyId,
+			name: &BoundedVec<u8, T::MaxNameLength>,
+		) -> DispatchResult {
+			let deposit = T::NameDepositPerByte::get().saturating_mul((name.len() as u32).into());
+			if deposit.is_zero() {
+				return Ok(())
+			}
+			T::Currency::reserve(who, deposit)?;
+			NameDeposits::<T>::insert(kitty_id, (who.clone(), deposit));
+			Ok(())
+		}
+
+		/// How long a kitty of `generation` rests after breeding.
+		pub fn breeding_cooldown(generation: u32) -> T::BlockNumber {
+			T::BreedingCooldownPerGeneration::get()
+				.saturating_mul(generation.into())
+				.saturating_add(T::BaseBreedingCooldown::get())
+				.min(T::MaxBreedingCooldown::get())
+		}
+
+		/// Blocks left until a kitty can breed again, zero if it can breed now.
+		pub fn breeding_cooldown_remaining(kitty_id: KittyId) -> T::BlockNumber {
+			let Some(last_bred) = Self::last_bred(kitty_id) else { return Zero::zero() };
+
+			let ready = last_bred
+				.saturating_add(Self::breeding_cooldown(Self::kitty_generation(kitty_id)));
+			ready.saturating_sub(<frame_system::Pallet<T>>::block_number())
+		}
+
+		/// The traits of a kitty, decoded from its DNA.
+		pub fn traits_of(kitty_id: KittyId) -> Option<KittyTraits> {
+			Self::kitties(kitty_id).map(|kitty| KittyTraits::from_dna(&kitty.dna))
+		}
+
+		/// How rare a kitty is among the living kitties. For every trait, the population is
+		/// divided by the number of kitties sharing the kitty's variant; the ratios are scaled by
+		/// `RARITY_SCALE` and summed, so a kitty sharing every trait with all others scores
+		/// `RARITY_SCALE` per trait.
+		pub fn rarity_score(kitty_id: KittyId) -> Option<u64> {
+			let traits = Self::traits_of(kitty_id)?;
+			let population = Self::kitty_population() as u64;
+
+			let score = traits
+				.variants()
+				.into_iter()
+				.enumerate()
+				.map(|(index, variant)| {
+					let count = Self::trait_count(index as u8, variant).max(1) as u64;
+					population.saturating_mul(RARITY_SCALE) / count
+				})
+				.sum();
+			Some(score)
+		}
+
+		/// Ancestors of a kitty up to `depth` generations back, nearest generation first. An
+		/// ancestor reached through both parents is listed once. Burned ancestors are listed and
+		/// traced back like any other.
+		pub fn ancestors(kitty_id: KittyId, depth: u32) -> Vec<KittyId> {
+			let mut seen = BTreeSet::new();
+			let mut ancestors = Vec::new();
+			let mut generation = Vec::from([kitty_id]);
+
+			for _ in 0..depth {
+				let mut parents = Vec::new();
+				for id in generation {
+					if let Some((parent_1, parent_2)) = Self::kitty_parents(id) {
+						for parent in [parent_1, parent_2] {
+							if seen.insert(parent) {
+								parents.push(parent);
+							}
+						}
+					}
+				}
+				if parents.is_empty() {
+					break
+				}
+				ancestors.extend_from_slice(&parents);
+				generation = parents;
+			}
+
+			ancestors
+		}
+
+		/// All descendants of a kitty, nearest generation first. A burned kitty stays in the tree,
+		/// and is listed, for as long as it has descendants.
+		pub fn descendants(kitty_id: KittyId) -> Vec<KittyId> {
+			let mut seen = BTreeSet::new();
+			let mut descendants = Vec::new();
+			let mut queue = VecDeque::from([kitty_id]);
+
+			while let Some(id) = queue.pop_front() {
+				for child in KittyChildren::<T>::iter_key_prefix(id) {
+					if seen.insert(child) {
+						descendants.push(child);
+						queue.push_back(child);
+					}
+				}
+			}
+
+			descendants
+		}
+
+		/// Append `owner` to the provenance log of a kitty, dropping the oldest entry if full.
+		fn record_provenance(kitty_id: KittyId, owner: &T::AccountId, acquisition: Acquisition) {
+			let record = ProvenanceRecord {
+				owner: owner.clone(),
+				block: <frame_system::Pallet<T>>::block_number(),
+				acquisition,
+			};
+			Provenance::<T>::mutate(kitty_id, |log| {
+				if let Err(record) = log.try_push(record) {
+					if !log.is_empty() {
+						log.remove(0);
+						let _ = log.try_push(record);
+					}
+				}
+			});
+		}
+
+		/// Count a new kitty with `dna` in the population and its trait counts.
+		fn note_kitty_added(dna: &[u8; 16]) {
+			KittyPopulation::<T>::mutate(|population| population.saturating_inc());
+			for (index, variant) in KittyTraits::from_dna(dna).variants().into_iter().enumerate() {
+				TraitCounts::<T>::mutate(index as u8, variant, |count| count.saturating_inc());
+			}
+		}
+
+		/// Remove a burned kitty with `dna` from the population and its trait counts.
+		fn note_kitty_removed(dna: &[u8; 16]) {
+			KittyPopulation::<T>::mutate(|population| population.saturating_dec());
+			for (index, variant) in KittyTraits::from_dna(dna).variants().into_iter().enumerate() {
+				TraitCounts::<T>::mutate(index as u8, variant, |count| count.saturating_dec());
+			}
+		}
+
+		/// Whether a kitty challenged another or has been challenged, until the challenge is
+		/// cancelled or its battle fought.
+		pub fn in_battle(kitty_id: KittyId) -> bool {
+			Challenges::<T>::contains_key(kitty_id) || ChallengingKitties::<T>::contains_key(kitty_id)
+		}
+
+		/// Battle power of a kitty: the sum of its DNA bytes plus `LEVEL_POWER` per level.
+		pub fn battle_power(kitty_id: KittyId) -> u32 {
+			let dna_power = Self::kitties(kitty_id)
+				.map(|kitty| kitty.dna.iter().map(|gene| *gene as u32).sum::<u32>())
+				.unwrap_or_default();
+			dna_power.saturating_add(Self::kitty_stats(kitty_id).level.saturating_mul(LEVEL_POWER))
+		}
+
+		/// Whether `challenger` beats `defender` with `seed`. Each side wins with a chance
+		/// proportional to its battle power; the same kitties and seed always give the same
+		/// outcome.
+		pub fn challenger_wins(challenger: KittyId, defender: KittyId, seed: &[u8; 16]) -> bool {
+			let power = Self::battle_power(challenger) as u64;
+			let total = power.saturating_add(Self::battle_power(defender) as u64).max(1);
+			let hash = (seed, challenger, defender).using_encoded(blake2_128);
+			let roll = u64::from_le_bytes(hash[..8].try_into().expect("hash is 16 bytes; qed"));
+			roll % total < power
+		}
+
+		/// Kitties owned by `owner`.
+		pub fn kitties_of(owner: &T::AccountId) -> Vec<KittyId> {
+			Self::owned_kitties(owner).into_inner()
+		}
+
+		/// Every listed kitty together with its asking price.
+		pub fn on_sale() -> Vec<(KittyId, BalanceOf<T>)> {
+			KittyOnSale::<T>::iter().collect()
+		}
+
+		/// The account with the usage rights of a kitty: the renter during a rental, otherwise
+		/// the owner.
+		pub fn kitty_user(kitty_id: KittyId) -> Option<T::AccountId> {
+			Self::rental(kitty_id)
+				.map(|rental| rental.renter)
+				.or_else(|| Self::kitty_owner(kitty_id))
+		}
+
+		/// Ensure `who` has the usage rights of a kitty.
+		fn ensure_kitty_user(who: &T::AccountId, kitty_id: KittyId) -> DispatchResult {
+			if let Some(rental) = Self::rental(kitty_id) {
+				ensure!(rental.renter == *who, Error::<T>::NotKittyUser);
+			} else {
+				let owner = Self::kitty_owner(kitty_id).ok_or(Error::<T>::InvalidKittyId)?;
+				ensure!(owner == *who, Error::<T>::NotOwner);
+			}
+			Ok(())
+		}
+
+		/// Create a new kitty with `dna` for `who`, charging `KittyPrice` and the name deposit.
+		fn do_create(
+			who: T::AccountId,
+			name: BoundedVec<u8, T::MaxNameLength>,
+			dna: [u8; 16],
+		) -> Result<KittyId, DispatchError> {
+			let price = T::KittyPrice::get();
+			T::Currency::transfer(&who, &Self::get_account_id(), price, ExistenceRequirement::KeepAlive)?;
+
+			let (kitty_id, kitty) =
+				Self::insert_new_kitty(&who, dna, name, None, Acquisition::Created, price)?;
+			Self::reserve_name_deposit(&who, kitty_id, &kitty.name)?;
+
+			Self::deposit_event(Event::KittyCreated { who, kitty_id, kitty });
+
+			Ok(kitty_id)
+		}
+
+		/// Breed a new kitty for `who` out of two different parents that are not resting, charging
+		/// `KittyPrice` and the name deposit. Each DNA bit is taken from the first parent where
+		/// `selector` is set. Usage rights and distinct parents are checked by the caller.
+		fn do_breed(
+			who: T::AccountId,
+			kitty_id_1: KittyId,
+			kitty_id_2: KittyId,
+			name: BoundedVec<u8, T::MaxNameLength>,
+			selector: [u8; 16],
+		) -> Result<KittyId, DispatchError> {
+			let kitty_1 = Self::kitties(kitty_id_1).ok_or(Error::<T>::InvalidKittyId)?;
+			let kitty_2 = Self::kitties(kitty_id_2).ok_or(Error::<T>::InvalidKittyId)?;
+			for kitty_id in [kitty_id_1, kitty_id_2] {
+				ensure!(
+					Self::breeding_cooldown_remaining(kitty_id).is_zero(),
+					Error::<T>::BreedingCooldown
+				);
+			}
+
+			let dna = Self::mix_dna(&kitty_1.dna, &kitty_2.dna, &selector);
+
+			let price = T::KittyPrice::get();
+			T::Currency::transfer(&who, &Self::get_account_id(), price, ExistenceRequirement::KeepAlive)?;
+
+			let (kitty_id, kitty) = Self::insert_new_kitty(
+				&who,
+				dna,
+				name,
+				Some((kitty_id_1, kitty_id_2)),
+				Acquisition::Bred,
+				price,
+			)?;
+			Self::reserve_name_deposit(&who, kitty_id, &kitty.name)?;
+			let now = <frame_system::Pallet<T>>::block_number();
+			LastBred::<T>::insert(kitty_id_1, now);
+			LastBred::<T>::insert(kitty_id_2, now);
+
+			Self::deposit_event(Event::KittyBreed { who, kitty_id, kitty });
+
+			Ok(kitty_id)
+		}
+
+		/// Store a new kitty for `owner` under the next id, with everything a new kitty needs: the
+		/// owner index, trait counts, lineage, provenance, the `paid` mint price and the
+		/// `OnKittyCreated` hook. Taking the payment, the name deposit and the event are left to
+		/// the caller.
+		pub(crate) fn insert_new_kitty(
+			owner: &T::AccountId,
+			dna: [u8; 16],
+			name: BoundedVec<u8, T::MaxNameLength>,
+			parents: Option<(KittyId, KittyId)>,
+			acquisition: Acquisition,
+			paid: BalanceOf<T>,
+		) -> Result<(KittyId, KittyOf<T>), DispatchError> {
+			let kitty_id = Self::get_next_id()?;
+			let kitty = Kitty { dna, name };
+
+			Self::note_kitty_added(&kitty.dna);
+			Kitties::<T>::insert(kitty_id, &kitty);
+			KittyOwner::<T>::insert(kitty_id, owner);
+			KittyCreator::<T>::insert(kitty_id, owner);
+			MintPrices::<T>::insert(kitty_id, paid);
+			Self::add_owned_kitty(owner, kitty_id)?;
+			Self::record_provenance(kitty_id, owner, acquisition);
+			if let Some((kitty_id_1, kitty_id_2)) = parents {
+				let generation = Self::kitty_generation(kitty_id_1)
+					.max(Self::kitty_generation(kitty_id_2))
+					.saturating_add(1);
+				KittyParents::<T>::insert(kitty_id, (kitty_id_1, kitty_id_2));
+				KittyChildren::<T>::insert(kitty_id_1, kitty_id, ());
+				KittyChildren::<T>::insert(kitty_id_2, kitty_id, ());
+				KittyGeneration::<T>::insert(kitty_id, generation);
+			}
+
+			T::OnKittyCreated::on_kitty_created(owner, kitty_id, parents);
+
+			Ok((kitty_id, kitty))
+		}
+
+		/// Whether `who` may move the kitty owned by `owner`.
+		pub fn is_approved(who: &T::AccountId, owner: &T::AccountId, kitty_id: KittyId) -> bool {
+			who == owner ||
+				Self::kitty_approval(kitty_id).as_ref() == Some(who) ||
+				OperatorApprovals::<T>::contains_key(owner, who)
+		}
+
+		/// List a kitty of `who` for sale at `price`, delisting it at `expiry` if given.
+		fn do_sale(
+			who: T::AccountId,
+			kitty_id: KittyId,
+			price: BalanceOf<T>,
+			expiry: Option<T::BlockNumber>,
+		) -> DispatchResult {
+			Self::kitties(kitty_id).ok_or::<DispatchError>(Error::<T>::InvalidKittyId.into())?;
+
+			ensure!(Self::kitty_owner(kitty_id) == Some(who.clone()), Error::<T>::NotOwner);
+			ensure!(Self::kitty_on_sale(kitty_id).is_none(), Error::<T>::AlreadyOnSale);
+			ensure!(!Auctions::<T>::contains_key(kitty_id), Error::<T>::KittyInAuction);
+			ensure!(!Rentals::<T>::contains_key(kitty_id), Error::<T>::KittyRented);
+			ensure!(!Self::in_battle(kitty_id), Error::<T>::KittyInBattle);
+
+			if let Some(expiry) = expiry {
+				ensure!(
+					expiry > <frame_system::Pallet<T>>::block_number(),
+					Error::<T>::InvalidSaleExpiry
+				);
+				SalesExpiring::<T>::try_mutate(expiry, |expiring| expiring.try_push(kitty_id))
+					.map_err(|_| Error::<T>::TooManyListingsExpiring)?;
+				SaleExpiry::<T>::insert(kitty_id, expiry);
+			}
+
+			<KittyOnSale<T>>::insert(kitty_id, price);
+			Self::deposit_event(Event::KittyOnSale { who, kitty_id, price });
+
+			Ok(())
+		}
+
+		/// Transfer a kitty that is neither in an auction nor rented out, delisting it if it was for
+		/// sale.
+		pub(crate) fn do_transfer(from: T::AccountId, to: T::AccountId, kitty_id: KittyId) -> DispatchResult {
+			ensure!(!Auctions::<T>::contains_key(kitty_id), Error::<T>::KittyInAuction);
+			ensure!(!Rentals::<T>::contains_key(kitty_id), Error::<T>::KittyRented);
+			ensure!(!Self::in_battle(kitty_id), Error::<T>::KittyInBattle);
+
+			Self::move_kitty(&from, &to, kitty_id, Acquisition::Transferred)?;
+			if Self::remove_listing(kitty_id) {
+				Self::deposit_event(Event::KittyDelisted { kitty_id });
+			}
+
+			Self::deposit_event(Event::KittyTransferred { from, to, kitty_id });
+
+			Ok(())
+		}
+
+		/// Remove a kitty and everything attached to it. Open offers and the name deposit are
+		/// released.
+		pub(crate) fn do_burn(owner: T::AccountId, kitty_id: KittyId, refund: BalanceOf<T>) {
+			Self::remove_listing(kitty_id);
+			for offer in Offers::<T>::take(kitty_id) {
+				T::Currency::unreserve(&offer.offerer, offer.amount);
+			}
+			Self::remove_owned_kitty(&owner, kitty_id);
+			KittyOwner::<T>::remove(kitty_id);
+			KittyCreator::<T>::remove(kitty_id);
+			MintPrices::<T>::remove(kitty_id);
+			KittyApprovals::<T>::remove(kitty_id);
+			RentalOffers::<T>::remove(kitty_id);
+			SiringOffers::<T>::remove(kitty_id);
+			if let Some(kitty) = Kitties::<T>::take(kitty_id) {
+				Self::note_kitty_removed(&kitty.dna);
+			}
+			if let Some((depositor, deposit)) = NameDeposits::<T>::take(kitty_id) {
+				T::Currency::unreserve(&depositor, deposit);
+			}
+			Self::prune_lineage(kitty_id);
+			KittyGeneration::<T>::remove(kitty_id);
+			Provenance::<T>::remove(kitty_id);
+			LastBred::<T>::remove(kitty_id);
+			Stats::<T>::remove(kitty_id);
+
+			T::OnKittyTransfer::on_kitty_burned(&owner, kitty_id);
+			Self::deposit_event(Event::KittyBurned { who: owner, kitty_id, refund });
+		}
+
+		/// Drop the family tree edges of a burned kitty once it has no children left, then do the
+		/// same for its parents. A burned kitty with children keeps its edges, so the lineage of
+		/// its descendants stays intact.
+		fn prune_lineage(kitty_id: KittyId) {
+			let mut burned = Vec::from([kitty_id]);
+
+			while let Some(id) = burned.pop() {
+				if Kitties::<T>::contains_key(id) ||
+					KittyChildren::<T>::iter_key_prefix(id).next().is_some()
+				{
+					continue
+				}
+				if let Some((parent_1, parent_2)) = KittyParents::<T>::take(id) {
+					KittyChildren::<T>::remove(parent_1, id);
+					KittyChildren::<T>::remove(parent_2, id);
+					burned.extend([parent_1, parent_2]);
+				}
+			}
+		}
+
+		/// Set a new name, moving the name deposit to `owner`.
+		pub(crate) fn do_rename(
+			owner: T::AccountId,
+			kitty_id: KittyId,
+			name: BoundedVec<u8, T::MaxNameLength>,
+		) -> DispatchResult {
+			let mut kitty = Self::kitties(kitty_id).ok_or(Error::<T>::InvalidKittyId)?;
+
+			if let Some((depositor, deposit)) = NameDeposits::<T>::take(kitty_id) {
+				T::Currency::unreserve(&depositor, deposit);
+			}
+			Self::reserve_name_deposit(&owner, kitty_id, &name)?;
+
+			kitty.name = name.clone();
+			Kitties::<T>::insert(kitty_id, kitty);
+
+			Self::deposit_event(Event::KittyRenamed { who: owner, kitty_id, name });
+
+			Ok(())
+		}
+
+		/// Move a kitty to a new owner, keep the owner index in sync and clear its approval.
+		fn move_kitty(
+			from: &T::AccountId,
+			to: &T::AccountId,
+			kitty_id: KittyId,
+			acquisition: Acquisition,
+		) -> DispatchResult {
+			Self::remove_owned_kitty(from, kitty_id);
+			Self::add_owned_kitty(to, kitty_id)?;
+			KittyOwner::<T>::insert(kitty_id, to);
+			Self::record_provenance(kitty_id, to, acquisition);
+			KittyApprovals::<T>::remove(kitty_id);
+			RentalOffers::<T>::remove(kitty_id);
+			SiringOffers::<T>::remove(kitty_id);
+			T::OnKittyTransfer::on_kitty_transfer(from, to, kitty_id);
+			Ok(())
+		}
+
+		/// Remove a listing and its expiry. Returns whether the kitty was listed.
+		fn remove_listing(kitty_id: KittyId) -> bool {
+			if let Some(expiry) = SaleExpiry::<T>::take(kitty_id) {
+				SalesExpiring::<T>::mutate(expiry, |expiring| expiring.retain(|id| *id != kitty_id));
+			}
+			KittyOnSale::<T>::take(kitty_id).is_some()
+		}
+
+		/// Hand the kitty to the best bidder and pay the seller out of the reserved bid. The
+		/// payments and the move happen together or not at all: if there is no bid, a payment
+		/// fails or the winner can not take the kitty, the bid is released and the kitty stays
+		/// with the seller.
+		pub(crate) fn settle_auction(kitty_id: KittyId) {
+			let Some(auction) = Auctions::<T>::take(kitty_id) else { return };
+
+			let Some((winner, price)) = auction.best_bid else {
+				Self::deposit_event(Event::AuctionUnsold { kitty_id });
+				return
+			};
+
+			let settled = frame_support::storage::with_storage_layer(|| {
+				let paid = Self::pay_for_kitty(&winner, &auction.seller, kitty_id, price, true)?;
+				Self::move_kitty(&auction.seller, &winner, kitty_id, Acquisition::Auction)?;
+				Ok::<_, DispatchError>(paid)
+			});
+
+			match settled {
+				Ok((fee, royalty)) => {
+					Self::deposit_event(Event::AuctionSettled { kitty_id, winner, price, fee, royalty });
+				},
+				Err(_) => {
+					T::Currency::unreserve(&winner, price);
+					Self::deposit_event(Event::AuctionUnsold { kitty_id });
+				},
+			}
+		}
+
+		/// Fight out the accepted challenge against `kitty_id`. The seed mixes the hash of the
+		/// block the challenge was accepted in, unknown to both sides when they committed. The
+		/// stake of the loser is paid to the winner from its reserve, as far as the reserve still
+		/// covers it; if nothing can be paid, both stakes are released and the challenge is
+		/// cancelled.
+		pub(crate) fn fight_battle(kitty_id: KittyId) {
+			let Some((defender, accepted_at)) = AcceptedChallenges::<T>::take(kitty_id) else {
+				return
+			};
+			let Some(challenge) = Challenges::<T>::take(kitty_id) else { return };
+			ChallengingKitties::<T>::remove(challenge.kitty_id);
+
+			let seed = (
+				T::Randomness::random_seed(),
+				<frame_system::Pallet<T>>::block_hash(accepted_at),
+				kitty_id,
+			)
+				.using_encoded(blake2_128);
+			let ((winner, winner_account), (loser, loser_account)) =
+				if Self::challenger_wins(challenge.kitty_id, kitty_id, &seed) {
+					((challenge.kitty_id, &challenge.challenger), (kitty_id, &defender))
+				} else {
+					((kitty_id, &defender), (challenge.kitty_id, &challenge.challenger))
+				};
+
+			// Pay out before the winner's own stake is released, so its account is alive.
+			let paid = T::Currency::repatriate_reserved(
+				loser_account,
+				winner_account,
+				challenge.stake,
+				BalanceStatus::Free,
+			)
+			.map(|remaining| challenge.stake.saturating_sub(remaining))
+			.unwrap_or_else(|_| Zero::zero());
+			T::Currency::unreserve(winner_account, challenge.stake);
+
+			if paid.is_zero() {
+				T::Currency::unreserve(loser_account, challenge.stake);
+				Self::deposit_event(Event::ChallengeCancelled {
+					kitty_id: challenge.kitty_id,
+					opponent: kitty_id,
+				});
+				return
+			}
+
+			let winner_level = Stats::<T>::mutate(winner, |stats| {
+				stats.wins.saturating_inc();
+				stats.experience.saturating_accrue(T::ExperiencePerWin::get());
+				stats.level = stats.experience / T::ExperiencePerLevel::get().max(1);
+				stats.level
+			});
+			Stats::<T>::mutate(loser, |stats| stats.losses.saturating_inc());
+
+			Self::deposit_event(Event::BattleFought { winner, loser, paid, winner_level });
+		}
+
+		/// Split a sale price into the marketplace fee and the creator royalty, if one is owed.
+		/// The seller gets the rest. No royalty is owed when the creator sells. A fee or royalty
+		/// that could not be paid to the pallet account or the creator, see `can_receive`, is
+		/// withheld and left to the seller.
+		pub fn sale_breakdown(
+			kitty_id: KittyId,
+			seller: &T::AccountId,
+			price: BalanceOf<T>,
+			reserved: bool,
+		) -> (BalanceOf<T>, Option<(T::AccountId, BalanceOf<T>)>) {
+			let fee = T::MarketplaceFee::get() * price;
+			let fee = if Self::can_receive(&Self::get_account_id(), fee, reserved) {
+				fee
+			} else {
+				Zero::zero()
+			};
+			let royalty = Self::kitty_creator(kitty_id)
+				.filter(|creator| creator != seller)
+				.map(|creator| (creator, T::CreatorRoyalty::get() * price))
+				.filter(|(creator, royalty)| {
+					!royalty.is_zero() && Self::can_receive(creator, *royalty, reserved)
+				});
+			(fee, royalty)
+		}
+
+		/// Whether `amount` can be paid to `who`. Out of a free balance the account must exist or
+		/// `amount` must be enough to create it; out of a reserved balance it must exist.
+		fn can_receive(who: &T::AccountId, amount: BalanceOf<T>, reserved: bool) -> bool {
+			!T::Currency::total_balance(who).is_zero() ||
+				(!reserved && amount >= T::Currency::minimum_balance())
+		}
+
+		/// Pay `price` for a kitty out of the free or, if `reserved`, the reserved balance of
+		/// `buyer`. Returns the marketplace fee and the creator royalty that were paid. Fails if
+		/// any part could not be paid in full; the caller's storage layer undoes the rest.
+		fn pay_for_kitty(
+			buyer: &T::AccountId,
+			seller: &T::AccountId,
+			kitty_id: KittyId,
+			price: BalanceOf<T>,
+			reserved: bool,
+		) -> Result<(BalanceOf<T>, BalanceOf<T>), DispatchError> {
+			let (fee, royalty) = Self::sale_breakdown(kitty_id, seller, price, reserved);
+			let royalty_amount = royalty.as_ref().map_or_else(Zero::zero, |(_, amount)| *amount);
+			let proceeds = price.saturating_sub(fee).saturating_sub(royalty_amount);
+
+			let payments = [(Self::get_account_id(), fee), (seller.clone(), proceeds)];
+
+			for (dest, amount) in payments.into_iter().chain(royalty) {
+				if amount.is_zero() {
+					continue
+				}
+				if reserved {
+					let remaining =
+						T::Currency::repatriate_reserved(buyer, &dest, amount, BalanceStatus::Free)?;
+					ensure!(remaining.is_zero(), Error::<T>::InsufficientReserve);
+				} else {
+					T::Currency::transfer(buyer, &dest, amount, ExistenceRequirement::KeepAlive)?;
+				}
+			}
+
+			Ok((fee, royalty_amount))
+		}
+
+		/// Remove the offer of `offerer` on a kitty.
+		fn take_offer(kitty_id: KittyId, offerer: &T::AccountId) -> Option<OfferOf<T>> {
+			Offers::<T>::mutate_exists(kitty_id, |maybe_offers| {
+				let offers = maybe_offers.as_mut()?;
+				let index = offers.iter().position(|offer| offer.offerer == *offerer)?;
+				let offer = offers.remove(index);
+				if offers.is_empty() {
+					*maybe_offers = None;
+				}
+				Some(offer)
+			})
+		}
+
+		/// Unreserve and drop the expired offers on a kitty, returning the ones still open.
+		fn purge_expired_offers(
+			kitty_id: KittyId,
+		) -> BoundedVec<OfferOf<T>, T::MaxOffersPerKitty> {
+			let now = <frame_system::Pallet<T>>::block_number();
+			let mut offers = Offers::<T>::get(kitty_id);
+			let count = offers.len();
+
+			offers.retain(|offer| {
+				if offer.expiry > now {
+					return true
+				}
+				T::Currency::unreserve(&offer.offerer, offer.amount);
+				Self::deposit_event(Event::OfferExpired {
+					who: offer.offerer.clone(),
+					kitty_id,
+					amount: offer.amount,
+				});
+				false
+			});
+
+			if offers.len() != count {
+				if offers.is_empty() {
+					Offers::<T>::remove(kitty_id);
+				} else {
+					Offers::<T>::insert(kitty_id, &offers);
+				}
+			}
+
+			offers
+		}
+
+		pub(crate) fn add_owned_kitty(owner: &T::AccountId, kitty_id: KittyId) -> DispatchResult {
+			OwnedKitties::<T>::try_mutate(owner, |kitties| {
+				kitties.try_push(kitty_id).map_err(|_| Error::<T>::TooManyKitties.into())
+			})
+		}
+
+		fn remove_owned_kitty(owner: &T::AccountId, kitty_id: KittyId) {
+			OwnedKitties::<T>::mutate(owner, |kitties| kitties.retain(|id| *id != kitty_id));
 		}
 
-		fn get_account_id() -> T::AccountId {
+		pub(crate) fn get_account_id() -> T::AccountId {
 			T::PalletId::get().into_account_truncating()
 		}
 	}
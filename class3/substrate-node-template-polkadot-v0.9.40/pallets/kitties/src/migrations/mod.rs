@@ -1 +1,18 @@
-pub mod v1;
\ No newline at end of file
+pub mod v1;
+pub mod v2;
+pub mod v3;
+pub mod v4;
+pub mod v5;
+pub mod v6;
+pub mod v7;
+
+/// Every storage migration of this pallet, in the order they have to run.
+pub type Migrations<T> = (
+    v1::MigrateToV1<T>,
+    v2::MigrateToV2<T>,
+    v3::MigrateToV3<T>,
+    v4::MigrateToV4<T>,
+    v5::MigrateToV5<T>,
+    v6::MigrateToV6<T>,
+    v7::MigrateToV7<T>,
+);
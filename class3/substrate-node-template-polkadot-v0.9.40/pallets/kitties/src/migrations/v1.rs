@@ -1,35 +1,79 @@
-use crate::{Config, Kitties, Pallet, Kitty, KittyId};
-use frame_support:: {
+use crate::{Config, KittyId, Pallet};
+use frame_support::{
     pallet_prelude::*,
-    storage::StoragePrefixedMap,
-    traits::GetStorageVersion,
+    traits::{GetStorageVersion, OnRuntimeUpgrade},
     weights::Weight,
 };
-
-// use frame_system::pallet_prelude::*;
-use frame_support::{migration::storage_key_iter, Blake2_128Concat};
+use sp_std::marker::PhantomData;
+#[cfg(feature = "try-runtime")]
+use sp_std::vec::Vec;
 
 #[derive(Encode, Decode, Clone, Copy, RuntimeDebug, PartialEq, Eq, Default, TypeInfo, MaxEncodedLen)]
 pub struct OldKitty(pub [u8; 16]);
 
-pub fn migrate<T: Config>() -> Weight {
-    let on_chain_version = Pallet::<T>::on_chain_storage_version();
-    let current_version = Pallet::<T>::current_storage_version();
+/// `Kitty` as stored from version 1 until names became variable-length in version 4.
+#[derive(Encode, Decode, Clone, Copy, RuntimeDebug, PartialEq, Eq, Default, TypeInfo, MaxEncodedLen)]
+pub struct KittyV1 {
+    pub dna: [u8; 16],
+    pub name: [u8; 4],
+}
+
+/// Name given to kitties that were created before kitties had names.
+pub const UNNAMED: [u8; 4] = [0u8; 4];
+
+/// `Kitties` with the version 1 value layout.
+#[frame_support::storage_alias]
+pub type Kitties<T: Config> = StorageMap<Pallet<T>, Blake2_128Concat, KittyId, KittyV1>;
+
+/// Migrate `Kitties` from a bare dna (`OldKitty`) to `KittyV1`. Existing kitties keep their dna and
+/// are named `UNNAMED`.
+pub struct MigrateToV1<T>(PhantomData<T>);
+
+impl<T: Config> OnRuntimeUpgrade for MigrateToV1<T> {
+    fn on_runtime_upgrade() -> Weight {
+        let on_chain_version = Pallet::<T>::on_chain_storage_version();
+
+        if on_chain_version != 0 {
+            return T::DbWeight::get().reads(1);
+        }
 
-    if on_chain_version != 0 { return Weight::zero(); }
+        let mut count = 0u64;
 
-    if current_version != 1 { return Weight::zero(); }
+        Kitties::<T>::translate::<OldKitty, _>(|_, kitty| {
+            count += 1;
+            Some(KittyV1 { dna: kitty.0, name: UNNAMED })
+        });
 
-    let module = Kitties::<T>::module_prefix();
-    let item = Kitties::<T>::storage_prefix();
+        StorageVersion::new(1).put::<Pallet<T>>();
 
-    for (index, kitty) in storage_key_iter::<KittyId, OldKitty, Blake2_128Concat>(module, item).drain() {
-        let new_kitty = Kitty {
-            dna: kitty.0,
-            name: *b"abcd",
+        T::DbWeight::get().reads_writes(count + 1, count + 1)
+    }
+
+    #[cfg(feature = "try-runtime")]
+    fn pre_upgrade() -> Result<Vec<u8>, &'static str> {
+        let count = if Pallet::<T>::on_chain_storage_version() == 0 {
+            Some(Kitties::<T>::iter_keys().count() as u32)
+        } else {
+            None
         };
-        Kitties::<T>::insert(index, new_kitty);
+
+        Ok(count.encode())
     }
 
-    Weight::zero()
-}
\ No newline at end of file
+    #[cfg(feature = "try-runtime")]
+    fn post_upgrade(state: Vec<u8>) -> Result<(), &'static str> {
+        let count = Option::<u32>::decode(&mut &state[..])
+            .map_err(|_| "v1: can not decode the pre-upgrade state")?;
+
+        if let Some(count) = count {
+            ensure!(Pallet::<T>::on_chain_storage_version() == 1, "v1: storage version not updated");
+            // `iter_values` skips entries that fail to decode.
+            ensure!(
+                Kitties::<T>::iter_values().count() as u32 == count,
+                "v1: kitties were lost or can not be decoded"
+            );
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,62 @@
+use crate::{BalanceOf, Config, KittyOnSale, Pallet};
+use frame_support::{
+    pallet_prelude::*,
+    traits::{GetStorageVersion, OnRuntimeUpgrade},
+    weights::Weight,
+};
+use sp_std::marker::PhantomData;
+#[cfg(feature = "try-runtime")]
+use sp_std::vec::Vec;
+
+/// `KittyOnSale` used to store `()` and every kitty was sold at `T::KittyPrice`. Keep that price
+/// for the kitties that are already listed.
+pub struct MigrateToV2<T>(PhantomData<T>);
+
+impl<T: Config> OnRuntimeUpgrade for MigrateToV2<T> {
+    fn on_runtime_upgrade() -> Weight {
+        let on_chain_version = Pallet::<T>::on_chain_storage_version();
+
+        if on_chain_version != 1 {
+            return T::DbWeight::get().reads(1);
+        }
+
+        let price: BalanceOf<T> = T::KittyPrice::get();
+        let mut count = 0u64;
+
+        KittyOnSale::<T>::translate::<(), _>(|_, ()| {
+            count += 1;
+            Some(price)
+        });
+
+        StorageVersion::new(2).put::<Pallet<T>>();
+
+        T::DbWeight::get().reads_writes(count + 1, count + 1)
+    }
+
+    #[cfg(feature = "try-runtime")]
+    fn pre_upgrade() -> Result<Vec<u8>, &'static str> {
+        let count = if Pallet::<T>::on_chain_storage_version() == 1 {
+            Some(KittyOnSale::<T>::iter_keys().count() as u32)
+        } else {
+            None
+        };
+
+        Ok(count.encode())
+    }
+
+    #[cfg(feature = "try-runtime")]
+    fn post_upgrade(state: Vec<u8>) -> Result<(), &'static str> {
+        let count = Option::<u32>::decode(&mut &state[..])
+            .map_err(|_| "v2: can not decode the pre-upgrade state")?;
+
+        if let Some(count) = count {
+            ensure!(Pallet::<T>::on_chain_storage_version() == 2, "v2: storage version not updated");
+            ensure!(
+                KittyOnSale::<T>::iter_values().count() as u32 == count,
+                "v2: listings were lost or can not be decoded"
+            );
+        }
+
+        Ok(())
+    }
+}
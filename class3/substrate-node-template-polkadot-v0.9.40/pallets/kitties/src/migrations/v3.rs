@@ -0,0 +1,76 @@
+use crate::{Config, KittyOwner, OwnedKitties, Pallet};
+use frame_support::{
+    log,
+    pallet_prelude::*,
+    traits::{GetStorageVersion, OnRuntimeUpgrade},
+    weights::Weight,
+};
+use sp_std::marker::PhantomData;
+#[cfg(feature = "try-runtime")]
+use sp_std::{collections::btree_map::BTreeMap, vec::Vec};
+
+/// Backfill the `OwnedKitties` index from `KittyOwner`. Kitties beyond `T::MaxKittiesOwned` for a
+/// single account can not be indexed; each one left out is logged.
+pub struct MigrateToV3<T>(PhantomData<T>);
+
+impl<T: Config> OnRuntimeUpgrade for MigrateToV3<T> {
+    fn on_runtime_upgrade() -> Weight {
+        let on_chain_version = Pallet::<T>::on_chain_storage_version();
+
+        if on_chain_version != 2 {
+            return T::DbWeight::get().reads(1);
+        }
+
+        let mut reads = 1u64;
+        let mut writes = 1u64;
+
+        for (kitty_id, owner) in KittyOwner::<T>::iter() {
+            reads += 2;
+            if OwnedKitties::<T>::try_mutate(&owner, |kitties| kitties.try_push(kitty_id)).is_ok() {
+                writes += 1;
+            } else {
+                log::warn!(
+                    target: "runtime::kitties",
+                    "v3: kitty {} left out of OwnedKitties, its owner has too many kitties",
+                    kitty_id,
+                );
+            }
+        }
+
+        StorageVersion::new(3).put::<Pallet<T>>();
+
+        T::DbWeight::get().reads_writes(reads, writes)
+    }
+
+    #[cfg(feature = "try-runtime")]
+    fn pre_upgrade() -> Result<Vec<u8>, &'static str> {
+        // The number of kitties that will be indexed: all of them, except those of an owner
+        // beyond `MaxKittiesOwned`, which the upgrade logs.
+        let expected = if Pallet::<T>::on_chain_storage_version() == 2 {
+            let mut owned = BTreeMap::<T::AccountId, u32>::new();
+            for owner in KittyOwner::<T>::iter_values() {
+                *owned.entry(owner).or_default() += 1;
+            }
+            let max = T::MaxKittiesOwned::get();
+            Some(owned.into_values().map(|count| count.min(max)).sum::<u32>())
+        } else {
+            None
+        };
+
+        Ok(expected.encode())
+    }
+
+    #[cfg(feature = "try-runtime")]
+    fn post_upgrade(state: Vec<u8>) -> Result<(), &'static str> {
+        let expected = Option::<u32>::decode(&mut &state[..])
+            .map_err(|_| "v3: can not decode the pre-upgrade state")?;
+
+        if let Some(expected) = expected {
+            ensure!(Pallet::<T>::on_chain_storage_version() == 3, "v3: storage version not updated");
+            let indexed: u32 = OwnedKitties::<T>::iter_values().map(|kitties| kitties.len() as u32).sum();
+            ensure!(indexed == expected, "v3: indexed kitties do not match owned kitties");
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,63 @@
+use super::v1::{KittyV1, UNNAMED};
+use crate::{Config, Kitties, Kitty, Pallet};
+use frame_support::{
+    pallet_prelude::*,
+    traits::{GetStorageVersion, OnRuntimeUpgrade},
+    weights::Weight,
+};
+use sp_std::{marker::PhantomData, vec::Vec};
+
+/// Migrate `Kitties` from the fixed 4-byte name of `KittyV1` to a name bounded by
+/// `T::MaxNameLength`. Existing names are kept, `UNNAMED` becomes an empty name. No name deposit
+/// is taken for migrated names.
+pub struct MigrateToV4<T>(PhantomData<T>);
+
+impl<T: Config> OnRuntimeUpgrade for MigrateToV4<T> {
+    fn on_runtime_upgrade() -> Weight {
+        let on_chain_version = Pallet::<T>::on_chain_storage_version();
+
+        if on_chain_version != 3 {
+            return T::DbWeight::get().reads(1);
+        }
+
+        let mut count = 0u64;
+
+        Kitties::<T>::translate::<KittyV1, _>(|_, kitty| {
+            count += 1;
+            let name = if kitty.name == UNNAMED { Vec::new() } else { kitty.name.to_vec() };
+            Some(Kitty { dna: kitty.dna, name: BoundedVec::truncate_from(name) })
+        });
+
+        StorageVersion::new(4).put::<Pallet<T>>();
+
+        T::DbWeight::get().reads_writes(count + 1, count + 1)
+    }
+
+    #[cfg(feature = "try-runtime")]
+    fn pre_upgrade() -> Result<Vec<u8>, &'static str> {
+        let count = if Pallet::<T>::on_chain_storage_version() == 3 {
+            Some(Kitties::<T>::iter_keys().count() as u32)
+        } else {
+            None
+        };
+
+        Ok(count.encode())
+    }
+
+    #[cfg(feature = "try-runtime")]
+    fn post_upgrade(state: Vec<u8>) -> Result<(), &'static str> {
+        let count = Option::<u32>::decode(&mut &state[..])
+            .map_err(|_| "v4: can not decode the pre-upgrade state")?;
+
+        if let Some(count) = count {
+            ensure!(Pallet::<T>::on_chain_storage_version() == 4, "v4: storage version not updated");
+            // `iter_values` skips entries that fail to decode.
+            ensure!(
+                Kitties::<T>::iter_values().count() as u32 == count,
+                "v4: kitties were lost or can not be decoded"
+            );
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,64 @@
+use crate::{Config, Kitties, KittyPopulation, KittyTraits, Pallet, TraitCounts};
+use frame_support::{
+    pallet_prelude::*,
+    traits::{GetStorageVersion, OnRuntimeUpgrade},
+    weights::Weight,
+};
+use sp_std::marker::PhantomData;
+#[cfg(feature = "try-runtime")]
+use sp_std::vec::Vec;
+
+/// Fill `KittyPopulation` and `TraitCounts` from the existing kitties, which were minted
+/// before trait counts were kept.
+pub struct MigrateToV5<T>(PhantomData<T>);
+
+impl<T: Config> OnRuntimeUpgrade for MigrateToV5<T> {
+    fn on_runtime_upgrade() -> Weight {
+        let on_chain_version = Pallet::<T>::on_chain_storage_version();
+
+        if on_chain_version != 4 {
+            return T::DbWeight::get().reads(1);
+        }
+
+        let mut count = 0u64;
+        let mut writes = 0u64;
+
+        for kitty in Kitties::<T>::iter_values() {
+            count += 1;
+            let variants = KittyTraits::from_dna(&kitty.dna).variants();
+            for (index, variant) in variants.into_iter().enumerate() {
+                TraitCounts::<T>::mutate(index as u8, variant, |n| *n = n.saturating_add(1));
+                writes += 1;
+            }
+        }
+
+        KittyPopulation::<T>::put(count as u32);
+        StorageVersion::new(5).put::<Pallet<T>>();
+
+        T::DbWeight::get().reads_writes(count + writes + 1, writes + 2)
+    }
+
+    #[cfg(feature = "try-runtime")]
+    fn pre_upgrade() -> Result<Vec<u8>, &'static str> {
+        let count = if Pallet::<T>::on_chain_storage_version() == 4 {
+            Some(Kitties::<T>::iter_keys().count() as u32)
+        } else {
+            None
+        };
+
+        Ok(count.encode())
+    }
+
+    #[cfg(feature = "try-runtime")]
+    fn post_upgrade(state: Vec<u8>) -> Result<(), &'static str> {
+        let count = Option::<u32>::decode(&mut &state[..])
+            .map_err(|_| "v5: can not decode the pre-upgrade state")?;
+
+        if let Some(count) = count {
+            ensure!(Pallet::<T>::on_chain_storage_version() == 5, "v5: storage version not updated");
+            ensure!(KittyPopulation::<T>::get() == count, "v5: population does not match kitties");
+        }
+
+        Ok(())
+    }
+}
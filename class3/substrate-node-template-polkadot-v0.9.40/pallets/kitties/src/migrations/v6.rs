@@ -0,0 +1,111 @@
+use crate::{
+    Acquisition, Config, KittyChildren, KittyCreator, KittyOwner, KittyParents, Pallet, Provenance,
+    ProvenanceRecord,
+};
+use frame_support::{
+    pallet_prelude::*,
+    traits::{GetStorageVersion, OnRuntimeUpgrade},
+    weights::Weight,
+};
+use sp_std::marker::PhantomData;
+#[cfg(feature = "try-runtime")]
+use sp_std::vec::Vec;
+
+/// Fill `KittyChildren` from `KittyParents`, and start the provenance log of every kitty with
+/// its current owner. When the kitty got there is unknown, so the record carries the upgrade
+/// block. It was transferred if a creator is recorded and it is not the owner; otherwise it was
+/// bred if it has parents and created if not. Kitties from before `KittyCreator` existed have no
+/// creator record, so they are taken to be held by their creator.
+pub struct MigrateToV6<T>(PhantomData<T>);
+
+impl<T: Config> OnRuntimeUpgrade for MigrateToV6<T> {
+    fn on_runtime_upgrade() -> Weight {
+        let on_chain_version = Pallet::<T>::on_chain_storage_version();
+
+        if on_chain_version != 5 {
+            return T::DbWeight::get().reads(1);
+        }
+
+        let mut reads = 1u64;
+        let mut writes = 1u64;
+
+        for (kitty_id, (parent_1, parent_2)) in KittyParents::<T>::iter() {
+            KittyChildren::<T>::insert(parent_1, kitty_id, ());
+            KittyChildren::<T>::insert(parent_2, kitty_id, ());
+            reads += 1;
+            writes += 2;
+        }
+
+        let block = <frame_system::Pallet<T>>::block_number();
+        for (kitty_id, owner) in KittyOwner::<T>::iter() {
+            let transferred =
+                KittyCreator::<T>::get(kitty_id).map_or(false, |creator| creator != owner);
+            let acquisition = if transferred {
+                Acquisition::Transferred
+            } else if KittyParents::<T>::contains_key(kitty_id) {
+                Acquisition::Bred
+            } else {
+                Acquisition::Created
+            };
+            let record = ProvenanceRecord { owner, block, acquisition };
+            Provenance::<T>::mutate(kitty_id, |log| {
+                if log.is_empty() {
+                    let _ = log.try_push(record);
+                }
+            });
+            reads += 4;
+            writes += 1;
+        }
+
+        StorageVersion::new(6).put::<Pallet<T>>();
+
+        T::DbWeight::get().reads_writes(reads, writes)
+    }
+
+    #[cfg(feature = "try-runtime")]
+    fn pre_upgrade() -> Result<Vec<u8>, &'static str> {
+        let counts = if Pallet::<T>::on_chain_storage_version() == 5 {
+            Some((
+                KittyParents::<T>::iter_keys().count() as u32,
+                KittyOwner::<T>::iter_keys().count() as u32,
+            ))
+        } else {
+            None
+        };
+
+        Ok(counts.encode())
+    }
+
+    #[cfg(feature = "try-runtime")]
+    fn post_upgrade(state: Vec<u8>) -> Result<(), &'static str> {
+        let counts = Option::<(u32, u32)>::decode(&mut &state[..])
+            .map_err(|_| "v6: can not decode the pre-upgrade state")?;
+
+        if let Some((parents, owned)) = counts {
+            ensure!(Pallet::<T>::on_chain_storage_version() == 6, "v6: storage version not updated");
+            for (kitty_id, (parent_1, parent_2)) in KittyParents::<T>::iter() {
+                ensure!(
+                    KittyChildren::<T>::contains_key(parent_1, kitty_id) &&
+                        KittyChildren::<T>::contains_key(parent_2, kitty_id),
+                    "v6: child missing from the children index"
+                );
+            }
+            ensure!(
+                KittyParents::<T>::iter_keys().count() as u32 == parents,
+                "v6: parents changed during the upgrade"
+            );
+            for (kitty_id, owner) in KittyOwner::<T>::iter() {
+                ensure!(
+                    Provenance::<T>::get(kitty_id).last().map(|record| &record.owner) == Some(&owner),
+                    "v6: provenance does not end with the owner"
+                );
+            }
+            ensure!(
+                KittyOwner::<T>::iter_keys().count() as u32 == owned,
+                "v6: owners changed during the upgrade"
+            );
+        }
+
+        Ok(())
+    }
+}
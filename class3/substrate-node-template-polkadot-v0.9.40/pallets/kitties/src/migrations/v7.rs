@@ -0,0 +1,75 @@
+use crate::{Config, Kitties, KittyGeneration, KittyId, KittyParents, Pallet};
+use frame_support::{
+    pallet_prelude::*,
+    traits::{GetStorageVersion, OnRuntimeUpgrade},
+    weights::Weight,
+};
+use sp_std::{collections::btree_map::BTreeMap, marker::PhantomData, vec::Vec};
+
+/// Fill `KittyGeneration` from `KittyParents` for the kitties bred before generations were
+/// kept. Children always have higher ids than their parents, so walking the family tree by id
+/// sees every parent first. Burned kitties still in the tree count towards their children's
+/// generation but get no entry of their own.
+pub struct MigrateToV7<T>(PhantomData<T>);
+
+impl<T: Config> OnRuntimeUpgrade for MigrateToV7<T> {
+    fn on_runtime_upgrade() -> Weight {
+        let on_chain_version = Pallet::<T>::on_chain_storage_version();
+
+        if on_chain_version != 6 {
+            return T::DbWeight::get().reads(1);
+        }
+
+        let mut bred: Vec<(KittyId, (KittyId, KittyId))> = KittyParents::<T>::iter().collect();
+        bred.sort_by_key(|(kitty_id, _)| *kitty_id);
+
+        let mut reads = 1 + bred.len() as u64;
+        let mut writes = 1u64;
+        let mut generations = BTreeMap::<KittyId, u32>::new();
+
+        for (kitty_id, (parent_1, parent_2)) in bred {
+            let generation_of = |id: KittyId| generations.get(&id).copied().unwrap_or_default();
+            let generation =
+                generation_of(parent_1).max(generation_of(parent_2)).saturating_add(1);
+            generations.insert(kitty_id, generation);
+
+            reads += 1;
+            if Kitties::<T>::contains_key(kitty_id) {
+                KittyGeneration::<T>::insert(kitty_id, generation);
+                writes += 1;
+            }
+        }
+
+        StorageVersion::new(7).put::<Pallet<T>>();
+
+        T::DbWeight::get().reads_writes(reads, writes)
+    }
+
+    #[cfg(feature = "try-runtime")]
+    fn pre_upgrade() -> Result<Vec<u8>, &'static str> {
+        Ok((Pallet::<T>::on_chain_storage_version() == 6).encode())
+    }
+
+    #[cfg(feature = "try-runtime")]
+    fn post_upgrade(state: Vec<u8>) -> Result<(), &'static str> {
+        let migrated = bool::decode(&mut &state[..])
+            .map_err(|_| "v7: can not decode the pre-upgrade state")?;
+
+        if migrated {
+            ensure!(Pallet::<T>::on_chain_storage_version() == 7, "v7: storage version not updated");
+            for (kitty_id, (parent_1, parent_2)) in KittyParents::<T>::iter() {
+                if !Kitties::<T>::contains_key(kitty_id) {
+                    continue
+                }
+                let generation = KittyGeneration::<T>::get(kitty_id);
+                ensure!(
+                    generation > KittyGeneration::<T>::get(parent_1) &&
+                        generation > KittyGeneration::<T>::get(parent_2),
+                    "v7: generation not above the parents'"
+                );
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -6,10 +6,11 @@ use sp_core::H256;
 use sp_runtime::{
 	testing::Header,
 	traits::{BlakeTwo256, IdentityLookup},
+	Perbill,
 };
 use pallet_insecure_randomness_collective_flip;
 use pallet_balances;
-use frame_support::{parameter_types, PalletId};
+use frame_support::{parameter_types, traits::Hooks, PalletId};
 
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
 type Block = frame_system::mocking::MockBlock<Test>;
@@ -79,18 +80,89 @@ pub type Balance = u128;
 parameter_types! {
 	pub KittyPalletId: PalletId = PalletId(*b"py/kitty");
 	pub KittyPrice: Balance = EXISTENTIAL_DEPOSIT * 10;
+	pub BurnRefund: Perbill = Perbill::from_percent(50);
+	pub MarketplaceFee: Perbill = Perbill::from_percent(5);
+	pub CreatorRoyalty: Perbill = Perbill::from_percent(10);
+	pub CommitmentForfeit: Perbill = Perbill::from_percent(20);
+	pub NameDepositPerByte: Balance = EXISTENTIAL_DEPOSIT;
+	pub static CreatedKitties: Vec<(u64, u32, Option<(u32, u32)>)> = vec![];
+	pub static KittyTransfers: Vec<(u64, Option<u64>, u32)> = vec![];
+	pub static RandomnessAvailable: bool = true;
 }
 
 impl pallet_kitties::Config for Test {
 	type RuntimeEvent = RuntimeEvent;
-	type Randomness = Randomness;
+	type Randomness = MockRandomness;
 	type Currency = Balances;
 	type KittyPrice = KittyPrice;
 	type PalletId = KittyPalletId;
+	type BaseBreedingCooldown = ConstU64<5>;
+	type BreedingCooldownPerGeneration = ConstU64<5>;
+	type MaxBreedingCooldown = ConstU64<20>;
+	type MaxNameLength = ConstU32<16>;
+	type NameDepositPerByte = NameDepositPerByte;
+	type MarketplaceFee = MarketplaceFee;
+	type CreatorRoyalty = CreatorRoyalty;
+	type BurnRefund = BurnRefund;
+	type MaxKittiesOwned = ConstU32<10>;
+	type MaxAuctionsPerBlock = ConstU32<10>;
+	type MaxOffersPerKitty = ConstU32<3>;
+	type MaxListingsExpiringPerBlock = ConstU32<10>;
+	type MaxRentalsEndingPerBlock = ConstU32<10>;
+	type RevealDelay = ConstU64<2>;
+	type CommitmentTimeout = ConstU64<10>;
+	type CommitmentForfeit = CommitmentForfeit;
+	type MaxCommitmentsExpiringPerBlock = ConstU32<10>;
+	type MaxBatch = ConstU32<5>;
+	type ExperiencePerWin = ConstU32<10>;
+	type ExperiencePerLevel = ConstU32<20>;
+	type MaxBattlesPerBlock = ConstU32<10>;
+	type MaxProvenanceLength = ConstU32<4>;
+	type OnKittyCreated = KittyHooks;
+	type OnKittyTransfer = KittyHooks;
+	type WeightInfo = ();
+}
+
+/// Records every kitty hook call so tests can inspect them. Burns are recorded as transfers
+/// to `None`.
+pub struct KittyHooks;
+
+impl pallet_kitties::OnKittyCreated<u64> for KittyHooks {
+	fn on_kitty_created(owner: &u64, kitty_id: u32, parents: Option<(u32, u32)>) {
+		let mut created = CreatedKitties::get();
+		created.push((*owner, kitty_id, parents));
+		CreatedKitties::set(created);
+	}
+}
+
+impl pallet_kitties::OnKittyTransfer<u64> for KittyHooks {
+	fn on_kitty_transfer(from: &u64, to: &u64, kitty_id: u32) {
+		let mut transfers = KittyTransfers::get();
+		transfers.push((*from, Some(*to), kitty_id));
+		KittyTransfers::set(transfers);
+	}
+
+	fn on_kitty_burned(owner: &u64, kitty_id: u32) {
+		let mut transfers = KittyTransfers::get();
+		transfers.push((*owner, None, kitty_id));
+		KittyTransfers::set(transfers);
+	}
 }
 
 impl pallet_insecure_randomness_collective_flip::Config for Test {}
 
+/// Hands out the collective flip randomness in the shape of BABE's `ParentBlockRandomness`,
+/// or no output at all while `RandomnessAvailable` is false.
+pub struct MockRandomness;
+
+impl frame_support::traits::Randomness<Option<H256>, u64> for MockRandomness {
+	fn random(subject: &[u8]) -> (Option<H256>, u64) {
+		let (seed, block) =
+			<Randomness as frame_support::traits::Randomness<H256, u64>>::random(subject);
+		(RandomnessAvailable::get().then_some(seed), block)
+	}
+}
+
 // Build genesis storage according to the mock runtime.
 use frame_support::storage::{StorageMap, StorageValue};
 
@@ -107,7 +179,7 @@ pub fn new_test_ext() -> sp_io::TestExternalities {
 
     // 设置初始余额
     let balances_config = pallet_balances::GenesisConfig::<Test> {
-        balances: vec![(1, 10000000), (2, 10000000), (3, 10000000)],
+        balances: vec![(1, 10000000), (2, 10000000), (3, 10000000), (4, 10000000), (5, 10000000)],
     };
 
     // 构建 GenesisConfig
@@ -129,3 +201,10 @@ pub fn new_test_ext() -> sp_io::TestExternalities {
 
 	ext
 }
+
+pub fn run_to_block(n: u64) {
+	while System::block_number() < n {
+		System::set_block_number(System::block_number() + 1);
+		KittiesModule::on_initialize(System::block_number());
+	}
+}
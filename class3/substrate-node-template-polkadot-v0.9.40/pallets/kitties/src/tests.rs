@@ -1,5 +1,11 @@
 use crate::{mock::*, Error, Event};
-use frame_support::{assert_noop, assert_ok};
+use frame_support::{
+	assert_noop, assert_ok,
+	traits::{Get, GetStorageVersion, OnRuntimeUpgrade, StorageVersion},
+};
+
+const KITTY_NAME: &[u8] = b"abcd";
+const SALE_PRICE: Balance = 8_000;
 
 #[test]
 fn it_works_for_create() {
@@ -8,7 +14,7 @@ fn it_works_for_create() {
 		let account_id = 1;
 
 		assert_eq!(KittiesModule::next_kitty_id(), kitty_id);
-		assert_ok!(KittiesModule::create(RuntimeOrigin::signed(account_id)));
+		assert_ok!(KittiesModule::create(RuntimeOrigin::signed(account_id), KITTY_NAME.to_vec()));
 
 		assert_eq!(KittiesModule::next_kitty_id(), kitty_id + 1);
 		assert_eq!(KittiesModule::kitties(kitty_id).is_some(), true);
@@ -18,7 +24,7 @@ fn it_works_for_create() {
 
 		crate::NextKittyId::<Test>::set(crate::KittyId::max_value());
 		assert_noop!(
-			KittiesModule::create(RuntimeOrigin::signed(account_id)),
+			KittiesModule::create(RuntimeOrigin::signed(account_id), KITTY_NAME.to_vec()),
 			Error::<Test>::InvalidKittyId
 		);
     });
@@ -30,7 +36,7 @@ fn it_works_for_kitty_created_event() {
 		let kitty_id = 0;
 		let account_id = 1;
 
-		assert_ok!(KittiesModule::create(RuntimeOrigin::signed(account_id)));
+		assert_ok!(KittiesModule::create(RuntimeOrigin::signed(account_id), KITTY_NAME.to_vec()));
 		let kitty = KittiesModule::kitties(kitty_id).unwrap();
 
 		System::assert_has_event(Event::KittyCreated { who: account_id, kitty_id, kitty }.into() );
@@ -44,22 +50,22 @@ fn it_works_for_breed() {
 		let account_id = 1;
 
 		assert_noop!(
-			KittiesModule::breed(RuntimeOrigin::signed(account_id), kitty_id, kitty_id),
+			KittiesModule::breed(RuntimeOrigin::signed(account_id), kitty_id, kitty_id, KITTY_NAME.to_vec()),
 			Error::<Test>::SameKittyId
 		);
 
 		assert_noop!(
-			KittiesModule::breed(RuntimeOrigin::signed(account_id), kitty_id, kitty_id + 1),
+			KittiesModule::breed(RuntimeOrigin::signed(account_id), kitty_id, kitty_id + 1, KITTY_NAME.to_vec()),
 			Error::<Test>::InvalidKittyId
 		);
 
-		assert_ok!(KittiesModule::create(RuntimeOrigin::signed(account_id)));
-		assert_ok!(KittiesModule::create(RuntimeOrigin::signed(account_id)));
+		assert_ok!(KittiesModule::create(RuntimeOrigin::signed(account_id), KITTY_NAME.to_vec()));
+		assert_ok!(KittiesModule::create(RuntimeOrigin::signed(account_id), KITTY_NAME.to_vec()));
 
 		assert_eq!(KittiesModule::next_kitty_id(), kitty_id + 2);
 
 		assert_ok!(
-			KittiesModule::breed(RuntimeOrigin::signed(account_id), kitty_id, kitty_id + 1)
+			KittiesModule::breed(RuntimeOrigin::signed(account_id), kitty_id, kitty_id + 1, KITTY_NAME.to_vec())
 		);
 
 		let breed_kitty_id = 2;
@@ -73,14 +79,66 @@ fn it_works_for_breed() {
 	});
 }
 
+#[test]
+fn it_works_for_breed_dna_inheritance() {
+	new_test_ext().execute_with(|| {
+		let kitty_id = 0;
+		let account_id = 1;
+
+		assert_ok!(KittiesModule::create(RuntimeOrigin::signed(account_id), KITTY_NAME.to_vec()));
+		assert_ok!(KittiesModule::create(RuntimeOrigin::signed(account_id), KITTY_NAME.to_vec()));
+
+		// 用固定的 dna 替换父母的随机 dna
+		let dna_1 = [0b1111_0000u8; 16];
+		let dna_2 = [0b0011_1100u8; 16];
+		crate::Kitties::<Test>::mutate(kitty_id, |kitty| kitty.as_mut().unwrap().dna = dna_1);
+		crate::Kitties::<Test>::mutate(kitty_id + 1, |kitty| kitty.as_mut().unwrap().dna = dna_2);
+
+		assert_ok!(
+			KittiesModule::breed(RuntimeOrigin::signed(account_id), kitty_id, kitty_id + 1, KITTY_NAME.to_vec())
+		);
+		let child = KittiesModule::kitties(kitty_id + 2).unwrap();
+
+		for i in 0..child.dna.len() {
+			// 父母共有的位一定遗传给子代
+			assert_eq!(child.dna[i] & (dna_1[i] & dna_2[i]), dna_1[i] & dna_2[i]);
+			// 父母都没有的位子代也不会有
+			assert_eq!(child.dna[i] & !(dna_1[i] | dna_2[i]), 0);
+		}
+
+		// 代数取父母中较大者加一
+		assert_eq!(KittiesModule::kitty_generation(kitty_id), 0);
+		assert_eq!(KittiesModule::kitty_generation(kitty_id + 1), 0);
+		assert_eq!(KittiesModule::kitty_generation(kitty_id + 2), 1);
+
+		// 等父代冷却结束
+		run_to_block(System::block_number() + KittiesModule::breeding_cooldown(0));
+		assert_ok!(
+			KittiesModule::breed(RuntimeOrigin::signed(account_id), kitty_id, kitty_id + 2, KITTY_NAME.to_vec())
+		);
+		assert_eq!(KittiesModule::kitty_generation(kitty_id + 3), 2);
+	});
+}
+
+#[test]
+fn it_works_for_mix_dna() {
+	new_test_ext().execute_with(|| {
+		let selector = [0b1010_0101u8; 16];
+
+		assert_eq!(KittiesModule::mix_dna(&[0xff; 16], &[0x00; 16], &selector), selector);
+		assert_eq!(KittiesModule::mix_dna(&[0x00; 16], &[0xff; 16], &selector), [0b0101_1010u8; 16]);
+		assert_eq!(KittiesModule::mix_dna(&[0x3c; 16], &[0x3c; 16], &selector), [0x3c; 16]);
+	});
+}
+
 #[test]
 fn it_works_for_kitty_breed_event() {
 	new_test_ext().execute_with(|| {
 		let kitty_id = 0;
 		let account_id = 1;
 
-		assert_ok!(KittiesModule::create(RuntimeOrigin::signed(account_id)));
-		assert_ok!(KittiesModule::create(RuntimeOrigin::signed(account_id)));
+		assert_ok!(KittiesModule::create(RuntimeOrigin::signed(account_id), KITTY_NAME.to_vec()));
+		assert_ok!(KittiesModule::create(RuntimeOrigin::signed(account_id), KITTY_NAME.to_vec()));
 		let kitty_1 = KittiesModule::kitties(kitty_id).unwrap();
 		let kitty_2 = KittiesModule::kitties(kitty_id + 1).unwrap();
 
@@ -92,7 +150,7 @@ fn it_works_for_kitty_breed_event() {
 		);
 
 		assert_ok!(
-			KittiesModule::breed(RuntimeOrigin::signed(account_id), kitty_id, kitty_id + 1)
+			KittiesModule::breed(RuntimeOrigin::signed(account_id), kitty_id, kitty_id + 1, KITTY_NAME.to_vec())
 		);
 		let breed_kitty = KittiesModule::kitties(kitty_id + 2).unwrap();
 		System::assert_has_event(
@@ -108,7 +166,7 @@ fn it_works_for_transfer() {
 		let account_id: u64 = 1;
 		let to_account_id: u64 = 2;
 
-		assert_ok!(KittiesModule::create(RuntimeOrigin::signed(account_id)));
+		assert_ok!(KittiesModule::create(RuntimeOrigin::signed(account_id), KITTY_NAME.to_vec()));
 		assert_eq!(
 			KittiesModule::kitty_owner(kitty_id).unwrap(),
             account_id
@@ -149,7 +207,7 @@ fn it_works_for_kitty_transferred_event() {
 		let account_id: u64 = 1;
 		let to_account_id: u64 = 2;
 
-		assert_ok!(KittiesModule::create(RuntimeOrigin::signed(account_id)));
+		assert_ok!(KittiesModule::create(RuntimeOrigin::signed(account_id), KITTY_NAME.to_vec()));
 		let kitty = KittiesModule::kitties(kitty_id).unwrap();
 		System::assert_has_event(
 			Event::KittyCreated { who: account_id, kitty_id, kitty }.into() 
@@ -178,7 +236,7 @@ fn it_works_for_sale() {
 		let account_id: u64 = 1;
 
 		// 创建kitty
-		assert_ok!(KittiesModule::create(RuntimeOrigin::signed(account_id)));
+		assert_ok!(KittiesModule::create(RuntimeOrigin::signed(account_id), KITTY_NAME.to_vec()));
 		assert_eq!(
 			KittiesModule::kitty_owner(kitty_id).unwrap(),
             account_id
@@ -186,7 +244,7 @@ fn it_works_for_sale() {
 
 		// sale成功
 		assert_ok!(
-			KittiesModule::sale(RuntimeOrigin::signed(account_id), kitty_id)
+			KittiesModule::sale(RuntimeOrigin::signed(account_id), kitty_id, SALE_PRICE, None)
 		);
 		assert_eq!(
 			KittiesModule::kitty_on_sale(kitty_id).is_some(),
@@ -195,19 +253,19 @@ fn it_works_for_sale() {
 
 		// sale时InvalidKittyId
 		assert_noop!(
-			KittiesModule::sale(RuntimeOrigin::signed(account_id), kitty_id + 1),
+			KittiesModule::sale(RuntimeOrigin::signed(account_id), kitty_id + 1, SALE_PRICE, None),
             Error::<Test>::InvalidKittyId
 		);
 
 		// sale时不是owner
 		assert_noop!(
-			KittiesModule::sale(RuntimeOrigin::signed(2u64), kitty_id),
+			KittiesModule::sale(RuntimeOrigin::signed(2u64), kitty_id, SALE_PRICE, None),
             Error::<Test>::NotOwner
 		);
 
 		// sale时已经AlreadyOnSale
 		assert_noop!(
-			KittiesModule::sale(RuntimeOrigin::signed(account_id), kitty_id),
+			KittiesModule::sale(RuntimeOrigin::signed(account_id), kitty_id, SALE_PRICE, None),
             Error::<Test>::AlreadyOnSale
 		);
 	});
@@ -219,14 +277,14 @@ fn it_works_for_kitty_sale_event() {
 		let kitty_id: u32 = 0;
 		let account_id: u64 = 1;
 
-		assert_ok!(KittiesModule::create(RuntimeOrigin::signed(account_id)));
+		assert_ok!(KittiesModule::create(RuntimeOrigin::signed(account_id), KITTY_NAME.to_vec()));
 		assert_eq!(
 			KittiesModule::kitty_owner(kitty_id).unwrap(),
             account_id
 		);
 
 		assert_ok!(
-			KittiesModule::sale(RuntimeOrigin::signed(account_id), kitty_id)
+			KittiesModule::sale(RuntimeOrigin::signed(account_id), kitty_id, SALE_PRICE, None)
 		);
 		assert_eq!(
 			KittiesModule::kitty_on_sale(kitty_id).is_some(),
@@ -234,7 +292,7 @@ fn it_works_for_kitty_sale_event() {
 		);
 
 		System::assert_has_event(
-			Event::KittyOnSale { who: account_id, kitty_id }.into() 
+			Event::KittyOnSale { who: account_id, kitty_id, price: SALE_PRICE }.into() 
 		);
 	});
 }
@@ -247,7 +305,7 @@ fn it_works_for_buy() {
 		let buy_account_id: u64 = 2;
 
 		// 创建 kitty
-		assert_ok!(KittiesModule::create(RuntimeOrigin::signed(sale_account_id)));
+		assert_ok!(KittiesModule::create(RuntimeOrigin::signed(sale_account_id), KITTY_NAME.to_vec()));
 		assert_eq!(
 			KittiesModule::kitty_owner(kitty_id).unwrap(),
             sale_account_id
@@ -255,13 +313,13 @@ fn it_works_for_buy() {
 
 		// buy时notOnSale
 		assert_noop!(
-			KittiesModule::buy(RuntimeOrigin::signed(buy_account_id), kitty_id),
+			KittiesModule::buy(RuntimeOrigin::signed(buy_account_id), kitty_id, SALE_PRICE),
             Error::<Test>::NotOnSale
 		);
 
 	    // sale kitty
 		assert_ok!(
-			KittiesModule::sale(RuntimeOrigin::signed(sale_account_id), kitty_id)
+			KittiesModule::sale(RuntimeOrigin::signed(sale_account_id), kitty_id, SALE_PRICE, None)
 		);
 		assert_eq!(
 			KittiesModule::kitty_on_sale(kitty_id).is_some(),
@@ -270,12 +328,12 @@ fn it_works_for_buy() {
 
 		// buy时InvalidKittyId
 		assert_noop!(
-			KittiesModule::sale(RuntimeOrigin::signed(buy_account_id), kitty_id + 1),
+			KittiesModule::sale(RuntimeOrigin::signed(buy_account_id), kitty_id + 1, SALE_PRICE, None),
             Error::<Test>::InvalidKittyId
 		);
 
 		// buy kitty 成功
-		assert_ok!(KittiesModule::buy(RuntimeOrigin::signed(buy_account_id), kitty_id));
+		assert_ok!(KittiesModule::buy(RuntimeOrigin::signed(buy_account_id), kitty_id, SALE_PRICE));
 		assert_eq!(
 			KittiesModule::kitty_owner(kitty_id).unwrap(),
             buy_account_id
@@ -283,7 +341,7 @@ fn it_works_for_buy() {
 
 		// buy时AlreadyOwned
 		assert_noop!(
-			KittiesModule::buy(RuntimeOrigin::signed(buy_account_id), kitty_id),
+			KittiesModule::buy(RuntimeOrigin::signed(buy_account_id), kitty_id, SALE_PRICE),
             Error::<Test>::AlreadyOwned
 		);
 	});
@@ -297,7 +355,7 @@ fn it_works_for_buy_event() {
 		let buy_account_id: u64 = 2;
 
 		// 创建 kitty
-		assert_ok!(KittiesModule::create(RuntimeOrigin::signed(sale_account_id)));
+		assert_ok!(KittiesModule::create(RuntimeOrigin::signed(sale_account_id), KITTY_NAME.to_vec()));
 		assert_eq!(
 			KittiesModule::kitty_owner(kitty_id).unwrap(),
             sale_account_id
@@ -305,7 +363,7 @@ fn it_works_for_buy_event() {
 
 	    // sale kitty
 		assert_ok!(
-			KittiesModule::sale(RuntimeOrigin::signed(sale_account_id), kitty_id)
+			KittiesModule::sale(RuntimeOrigin::signed(sale_account_id), kitty_id, SALE_PRICE, None)
 		);
 		assert_eq!(
 			KittiesModule::kitty_on_sale(kitty_id).is_some(),
@@ -313,14 +371,2124 @@ fn it_works_for_buy_event() {
 		);
 
 		// buy kitty 成功
-		assert_ok!(KittiesModule::buy(RuntimeOrigin::signed(buy_account_id), kitty_id));
+		assert_ok!(KittiesModule::buy(RuntimeOrigin::signed(buy_account_id), kitty_id, SALE_PRICE));
 		assert_eq!(
 			KittiesModule::kitty_owner(kitty_id).unwrap(),
             buy_account_id
 		);
 
 		System::assert_has_event(
-			Event::KittyBought { who: buy_account_id, kitty_id }.into() 
+			Event::KittyBought {
+				who: buy_account_id,
+				kitty_id,
+				price: SALE_PRICE,
+				fee: MarketplaceFee::get() * SALE_PRICE,
+				royalty: 0,
+			}
+			.into()
+		);
+	});
+}
+
+#[test]
+fn it_works_for_buy_at_asking_price() {
+	new_test_ext().execute_with(|| {
+		let kitty_id: u32 = 0;
+		let sale_account_id: u64 = 1;
+		let buy_account_id: u64 = 2;
+
+		assert_ok!(KittiesModule::create(RuntimeOrigin::signed(sale_account_id), KITTY_NAME.to_vec()));
+		assert_ok!(KittiesModule::sale(RuntimeOrigin::signed(sale_account_id), kitty_id, SALE_PRICE, None));
+		assert_eq!(KittiesModule::kitty_on_sale(kitty_id), Some(SALE_PRICE));
+
+		// 出价低于要价
+		assert_noop!(
+			KittiesModule::buy(RuntimeOrigin::signed(buy_account_id), kitty_id, SALE_PRICE - 1),
+			Error::<Test>::PriceTooHigh
+		);
+
+		let sale_balance = Balances::free_balance(sale_account_id);
+		let buy_balance = Balances::free_balance(buy_account_id);
+
+		// 出价高于要价时只按要价付款
+		assert_ok!(KittiesModule::buy(RuntimeOrigin::signed(buy_account_id), kitty_id, SALE_PRICE * 2));
+		// 首次出售不付版税, 只扣市场手续费
+		let fee = MarketplaceFee::get() * SALE_PRICE;
+		assert_eq!(Balances::free_balance(sale_account_id), sale_balance + SALE_PRICE - fee);
+		assert_eq!(Balances::free_balance(buy_account_id), buy_balance - SALE_PRICE);
+		assert_eq!(KittiesModule::kitty_on_sale(kitty_id), None);
+	});
+}
+
+#[test]
+fn it_works_for_update_price() {
+	new_test_ext().execute_with(|| {
+		let kitty_id: u32 = 0;
+		let account_id: u64 = 1;
+		let new_price: Balance = SALE_PRICE * 2;
+
+		assert_ok!(KittiesModule::create(RuntimeOrigin::signed(account_id), KITTY_NAME.to_vec()));
+
+		// 未上架时不能改价
+		assert_noop!(
+			KittiesModule::update_price(RuntimeOrigin::signed(account_id), kitty_id, new_price),
+			Error::<Test>::NotOnSale
+		);
+
+		assert_ok!(KittiesModule::sale(RuntimeOrigin::signed(account_id), kitty_id, SALE_PRICE, None));
+
+		assert_noop!(
+			KittiesModule::update_price(RuntimeOrigin::signed(2u64), kitty_id, new_price),
+			Error::<Test>::NotOwner
+		);
+		assert_noop!(
+			KittiesModule::update_price(RuntimeOrigin::signed(account_id), kitty_id + 1, new_price),
+			Error::<Test>::InvalidKittyId
+		);
+
+		assert_ok!(KittiesModule::update_price(RuntimeOrigin::signed(account_id), kitty_id, new_price));
+		assert_eq!(KittiesModule::kitty_on_sale(kitty_id), Some(new_price));
+		System::assert_has_event(
+			Event::KittyPriceUpdated { who: account_id, kitty_id, price: new_price }.into()
+		);
+
+		assert_noop!(
+			KittiesModule::buy(RuntimeOrigin::signed(2u64), kitty_id, SALE_PRICE),
+			Error::<Test>::PriceTooHigh
+		);
+	});
+}
+
+#[test]
+fn it_works_for_v2_migration() {
+	new_test_ext().execute_with(|| {
+		let kitty_id: u32 = 0;
+
+		// 模拟 v1 的存储: KittyOnSale 的值为 ()
+		StorageVersion::new(1).put::<KittiesModule>();
+		frame_support::storage::unhashed::put_raw(
+			&crate::KittyOnSale::<Test>::hashed_key_for(kitty_id),
+			&[],
+		);
+
+		crate::migrations::v2::MigrateToV2::<Test>::on_runtime_upgrade();
+
+		assert_eq!(KittiesModule::kitty_on_sale(kitty_id), Some(KittyPrice::get()));
+		assert_eq!(KittiesModule::on_chain_storage_version(), 2);
+	});
+}
+
+#[test]
+fn it_works_for_owned_kitties() {
+	new_test_ext().execute_with(|| {
+		let kitty_id: u32 = 0;
+		let account_id: u64 = 1;
+		let other_account_id: u64 = 2;
+
+		assert_ok!(KittiesModule::create(RuntimeOrigin::signed(account_id), KITTY_NAME.to_vec()));
+		assert_ok!(KittiesModule::create(RuntimeOrigin::signed(account_id), KITTY_NAME.to_vec()));
+		assert_ok!(
+			KittiesModule::breed(RuntimeOrigin::signed(account_id), kitty_id, kitty_id + 1, KITTY_NAME.to_vec())
+		);
+		assert_eq!(KittiesModule::owned_kitties(account_id).into_inner(), vec![0, 1, 2]);
+
+		// transfer 后索引同步更新
+		assert_ok!(
+			KittiesModule::transfer(RuntimeOrigin::signed(account_id), other_account_id, kitty_id)
+		);
+		assert_eq!(KittiesModule::owned_kitties(account_id).into_inner(), vec![1, 2]);
+		assert_eq!(KittiesModule::owned_kitties(other_account_id).into_inner(), vec![0]);
+
+		// buy 后索引同步更新
+		assert_ok!(KittiesModule::sale(RuntimeOrigin::signed(account_id), kitty_id + 1, SALE_PRICE, None));
+		assert_ok!(
+			KittiesModule::buy(RuntimeOrigin::signed(other_account_id), kitty_id + 1, SALE_PRICE)
+		);
+		assert_eq!(KittiesModule::owned_kitties(account_id).into_inner(), vec![2]);
+		assert_eq!(KittiesModule::owned_kitties(other_account_id).into_inner(), vec![0, 1]);
+	});
+}
+
+#[test]
+fn it_fails_for_too_many_kitties() {
+	new_test_ext().execute_with(|| {
+		let account_id: u64 = 1;
+		let other_account_id: u64 = 2;
+		let max_owned = <Test as crate::Config>::MaxKittiesOwned::get();
+
+		for _ in 0..max_owned {
+			assert_ok!(KittiesModule::create(RuntimeOrigin::signed(account_id), KITTY_NAME.to_vec()));
+		}
+		assert_noop!(
+			KittiesModule::create(RuntimeOrigin::signed(account_id), KITTY_NAME.to_vec()),
+			Error::<Test>::TooManyKitties
+		);
+		assert_noop!(
+			KittiesModule::breed(RuntimeOrigin::signed(account_id), 0, 1, KITTY_NAME.to_vec()),
+			Error::<Test>::TooManyKitties
+		);
+
+		assert_ok!(KittiesModule::create(RuntimeOrigin::signed(other_account_id), KITTY_NAME.to_vec()));
+		assert_noop!(
+			KittiesModule::transfer(RuntimeOrigin::signed(other_account_id), account_id, max_owned),
+			Error::<Test>::TooManyKitties
+		);
+	});
+}
+
+#[test]
+fn it_works_for_v3_migration() {
+	new_test_ext().execute_with(|| {
+		let account_id: u64 = 1;
+		let other_account_id: u64 = 2;
+
+		// 模拟 v2 的存储: 只有 KittyOwner 没有 OwnedKitties
+		StorageVersion::new(2).put::<KittiesModule>();
+		crate::KittyOwner::<Test>::insert(0, account_id);
+		crate::KittyOwner::<Test>::insert(1, other_account_id);
+		crate::KittyOwner::<Test>::insert(2, account_id);
+
+		crate::migrations::v3::MigrateToV3::<Test>::on_runtime_upgrade();
+
+		let mut owned = KittiesModule::owned_kitties(account_id).into_inner();
+		owned.sort();
+		assert_eq!(owned, vec![0, 2]);
+		assert_eq!(KittiesModule::owned_kitties(other_account_id).into_inner(), vec![1]);
+		assert_eq!(KittiesModule::on_chain_storage_version(), 3);
+	});
+}
+
+#[test]
+fn it_works_for_query_helpers() {
+	new_test_ext().execute_with(|| {
+		let account_id: u64 = 1;
+		let other_account_id: u64 = 2;
+
+		assert_ok!(KittiesModule::create(RuntimeOrigin::signed(account_id), KITTY_NAME.to_vec()));
+		assert_ok!(KittiesModule::create(RuntimeOrigin::signed(other_account_id), KITTY_NAME.to_vec()));
+		assert_ok!(KittiesModule::sale(RuntimeOrigin::signed(other_account_id), 1, SALE_PRICE, None));
+
+		assert_eq!(KittiesModule::kitties_of(&account_id), vec![0]);
+		assert_eq!(KittiesModule::kitties_of(&other_account_id), vec![1]);
+		assert_eq!(KittiesModule::on_sale(), vec![(1, SALE_PRICE)]);
+	});
+}
+
+#[test]
+fn it_works_for_v1_migration() {
+	new_test_ext().execute_with(|| {
+		use crate::migrations::v1::{Kitties, KittyV1, MigrateToV1, OldKitty, UNNAMED};
+
+		// 模拟 v0 的存储: Kitties 的值只有 dna
+		StorageVersion::new(0).put::<KittiesModule>();
+		for kitty_id in 0..3u32 {
+			frame_support::storage::unhashed::put(
+				&crate::Kitties::<Test>::hashed_key_for(kitty_id),
+				&OldKitty([kitty_id as u8; 16]),
+			);
+		}
+
+		let weight = MigrateToV1::<Test>::on_runtime_upgrade();
+
+		for kitty_id in 0..3u32 {
+			assert_eq!(
+				Kitties::<Test>::get(kitty_id),
+				Some(KittyV1 { dna: [kitty_id as u8; 16], name: UNNAMED })
+			);
+		}
+		assert_eq!(KittiesModule::on_chain_storage_version(), 1);
+		assert_eq!(weight, <Test as frame_system::Config>::DbWeight::get().reads_writes(4, 4));
+
+		// 再次执行不会改动存储
+		let weight = MigrateToV1::<Test>::on_runtime_upgrade();
+		assert_eq!(weight, <Test as frame_system::Config>::DbWeight::get().reads(1));
+		assert_eq!(Kitties::<Test>::iter().count(), 3);
+	});
+}
+
+#[test]
+fn it_works_for_v4_migration() {
+	new_test_ext().execute_with(|| {
+		use crate::migrations::{
+			v1::{KittyV1, UNNAMED},
+			v4::MigrateToV4,
+		};
+
+		// 模拟 v3 的存储: 名字固定为 4 个字节
+		StorageVersion::new(3).put::<KittiesModule>();
+		frame_support::storage::unhashed::put(
+			&crate::Kitties::<Test>::hashed_key_for(0),
+			&KittyV1 { dna: [1u8; 16], name: *b"abcd" },
+		);
+		frame_support::storage::unhashed::put(
+			&crate::Kitties::<Test>::hashed_key_for(1),
+			&KittyV1 { dna: [2u8; 16], name: UNNAMED },
+		);
+
+		let weight = MigrateToV4::<Test>::on_runtime_upgrade();
+
+		let kitty = KittiesModule::kitties(0).unwrap();
+		assert_eq!(kitty.dna, [1u8; 16]);
+		assert_eq!(kitty.name.into_inner(), b"abcd".to_vec());
+		assert!(KittiesModule::kitties(1).unwrap().name.is_empty());
+		assert_eq!(KittiesModule::name_deposit(0), None);
+		assert_eq!(KittiesModule::on_chain_storage_version(), 4);
+		assert_eq!(weight, <Test as frame_system::Config>::DbWeight::get().reads_writes(3, 3));
+
+		// 再次执行不会改动存储
+		let weight = MigrateToV4::<Test>::on_runtime_upgrade();
+		assert_eq!(weight, <Test as frame_system::Config>::DbWeight::get().reads(1));
+	});
+}
+
+#[test]
+fn it_works_for_v5_migration() {
+	new_test_ext().execute_with(|| {
+		use crate::migrations::v5::MigrateToV5;
+
+		// 模拟 v4 的存储: 小猫存在但没有统计特征
+		StorageVersion::new(4).put::<KittiesModule>();
+		crate::Kitties::<Test>::insert(0, crate::Kitty { dna: [1u8; 16], name: Default::default() });
+		crate::Kitties::<Test>::insert(1, crate::Kitty { dna: [1u8; 16], name: Default::default() });
+
+		MigrateToV5::<Test>::on_runtime_upgrade();
+
+		assert_eq!(KittiesModule::kitty_population(), 2);
+		assert_eq!(KittiesModule::trait_count(0, 1), 2);
+		assert_eq!(KittiesModule::rarity_score(0), Some(600));
+		assert_eq!(KittiesModule::on_chain_storage_version(), 5);
+
+		// 再次执行不会重复统计
+		MigrateToV5::<Test>::on_runtime_upgrade();
+		assert_eq!(KittiesModule::kitty_population(), 2);
+	});
+}
+
+#[test]
+fn it_works_for_v6_migration() {
+	new_test_ext().execute_with(|| {
+		use crate::migrations::v6::MigrateToV6;
+
+		// 模拟 v5 的存储: 只有父母记录, 没有子女索引
+		StorageVersion::new(5).put::<KittiesModule>();
+		crate::KittyParents::<Test>::insert(2, (0, 1));
+		crate::KittyParents::<Test>::insert(3, (0, 2));
+		crate::KittyParents::<Test>::insert(5, (0, 2));
+		// 小猫 0 由 1 创建并持有, 小猫 2 由 1 繁殖并持有, 小猫 3 由 1 繁殖后转给了 2
+		for (kitty_id, owner) in [(0u32, 1u64), (2, 1), (3, 2)] {
+			crate::KittyOwner::<Test>::insert(kitty_id, owner);
+			crate::KittyCreator::<Test>::insert(kitty_id, 1);
+		}
+		// 小猫 4 和 5 没有创建者记录, 按创建和繁殖处理
+		crate::KittyOwner::<Test>::insert(4, 3);
+		crate::KittyOwner::<Test>::insert(5, 3);
+
+		MigrateToV6::<Test>::on_runtime_upgrade();
+
+		assert!(crate::KittyChildren::<Test>::contains_key(0, 2));
+		assert!(crate::KittyChildren::<Test>::contains_key(1, 2));
+		assert!(crate::KittyChildren::<Test>::contains_key(0, 3));
+		assert!(crate::KittyChildren::<Test>::contains_key(2, 3));
+
+		// 每只小猫的来历以当前主人开始
+		for (kitty_id, owner, acquisition) in [
+			(0u32, 1u64, crate::Acquisition::Created),
+			(2, 1, crate::Acquisition::Bred),
+			(3, 2, crate::Acquisition::Transferred),
+			(4, 3, crate::Acquisition::Created),
+			(5, 3, crate::Acquisition::Bred),
+		] {
+			assert_eq!(
+				KittiesModule::provenance(kitty_id).into_inner(),
+				vec![crate::ProvenanceRecord { owner, block: 1, acquisition }]
+			);
+		}
+		assert!(KittiesModule::provenance(1).is_empty());
+		assert_eq!(KittiesModule::on_chain_storage_version(), 6);
+	});
+}
+
+#[test]
+fn it_works_for_v7_migration() {
+	new_test_ext().execute_with(|| {
+		use crate::migrations::v7::MigrateToV7;
+
+		for _ in 0..6 {
+			assert_ok!(KittiesModule::create(RuntimeOrigin::signed(1), KITTY_NAME.to_vec()));
+		}
+
+		// 模拟 v6 的存储: 有父母记录, 没有代数; 小猫 4 已经销毁但仍是小猫 5 的父母
+		StorageVersion::new(6).put::<KittiesModule>();
+		crate::Kitties::<Test>::remove(4);
+		crate::KittyParents::<Test>::insert(2, (0, 1));
+		crate::KittyParents::<Test>::insert(3, (0, 2));
+		crate::KittyParents::<Test>::insert(4, (3, 1));
+		crate::KittyParents::<Test>::insert(5, (4, 0));
+
+		MigrateToV7::<Test>::on_runtime_upgrade();
+
+		// 代数比年长的父母多一代
+		assert_eq!(KittiesModule::kitty_generation(0), 0);
+		assert_eq!(KittiesModule::kitty_generation(2), 1);
+		assert_eq!(KittiesModule::kitty_generation(3), 2);
+		assert!(!crate::KittyGeneration::<Test>::contains_key(4));
+		assert_eq!(KittiesModule::kitty_generation(5), 4);
+		assert_eq!(KittiesModule::on_chain_storage_version(), 7);
+	});
+}
+
+#[test]
+fn it_works_for_all_migrations() {
+	new_test_ext().execute_with(|| {
+		use crate::migrations::{v1::OldKitty, Migrations};
+
+		let kitty_id: u32 = 0;
+		let account_id: u64 = 1;
+
+		StorageVersion::new(0).put::<KittiesModule>();
+		frame_support::storage::unhashed::put(
+			&crate::Kitties::<Test>::hashed_key_for(kitty_id),
+			&OldKitty([7u8; 16]),
+		);
+		crate::KittyOwner::<Test>::insert(kitty_id, account_id);
+		frame_support::storage::unhashed::put_raw(
+			&crate::KittyOnSale::<Test>::hashed_key_for(kitty_id),
+			&[],
+		);
+
+		Migrations::<Test>::on_runtime_upgrade();
+
+		assert_eq!(KittiesModule::kitties(kitty_id).unwrap().dna, [7u8; 16]);
+		assert!(KittiesModule::kitties(kitty_id).unwrap().name.is_empty());
+		assert_eq!(KittiesModule::kitty_on_sale(kitty_id), Some(KittyPrice::get()));
+		assert_eq!(KittiesModule::kitties_of(&account_id), vec![kitty_id]);
+		assert_eq!(KittiesModule::kitty_population(), 1);
+		assert_eq!(KittiesModule::on_chain_storage_version(), KittiesModule::current_storage_version());
+	});
+}
+
+#[test]
+fn it_works_for_create_auction() {
+	new_test_ext().execute_with(|| {
+		let kitty_id: u32 = 0;
+		let account_id: u64 = 1;
+		let end_block: u64 = 10;
+
+		assert_ok!(KittiesModule::create(RuntimeOrigin::signed(account_id), KITTY_NAME.to_vec()));
+
+		assert_noop!(
+			KittiesModule::create_auction(RuntimeOrigin::signed(2u64), kitty_id, SALE_PRICE, end_block),
+			Error::<Test>::NotOwner
+		);
+		assert_noop!(
+			KittiesModule::create_auction(RuntimeOrigin::signed(account_id), kitty_id, SALE_PRICE, 1),
+			Error::<Test>::InvalidAuctionEnd
+		);
+
+		assert_ok!(
+			KittiesModule::create_auction(RuntimeOrigin::signed(account_id), kitty_id, SALE_PRICE, end_block)
+		);
+		assert_eq!(KittiesModule::auctions_ending(end_block).into_inner(), vec![kitty_id]);
+		System::assert_has_event(
+			Event::AuctionCreated { who: account_id, kitty_id, min_bid: SALE_PRICE, end_block }.into()
+		);
+
+		// 拍卖期间不能转移、出售或重复拍卖
+		assert_noop!(
+			KittiesModule::transfer(RuntimeOrigin::signed(account_id), 2u64, kitty_id),
+			Error::<Test>::KittyInAuction
+		);
+		assert_noop!(
+			KittiesModule::sale(RuntimeOrigin::signed(account_id), kitty_id, SALE_PRICE, None),
+			Error::<Test>::KittyInAuction
+		);
+		assert_noop!(
+			KittiesModule::create_auction(RuntimeOrigin::signed(account_id), kitty_id, SALE_PRICE, end_block),
+			Error::<Test>::KittyInAuction
+		);
+	});
+}
+
+#[test]
+fn it_works_for_bid() {
+	new_test_ext().execute_with(|| {
+		let kitty_id: u32 = 0;
+		let seller: u64 = 1;
+		let bidder_1: u64 = 2;
+		let bidder_2: u64 = 3;
+		let end_block: u64 = 10;
+
+		assert_noop!(
+			KittiesModule::bid(RuntimeOrigin::signed(bidder_1), kitty_id, SALE_PRICE),
+			Error::<Test>::NotInAuction
+		);
+
+		assert_ok!(KittiesModule::create(RuntimeOrigin::signed(seller), KITTY_NAME.to_vec()));
+		assert_ok!(
+			KittiesModule::create_auction(RuntimeOrigin::signed(seller), kitty_id, SALE_PRICE, end_block)
+		);
+
+		assert_noop!(
+			KittiesModule::bid(RuntimeOrigin::signed(seller), kitty_id, SALE_PRICE),
+			Error::<Test>::AlreadyOwned
+		);
+		assert_noop!(
+			KittiesModule::bid(RuntimeOrigin::signed(bidder_1), kitty_id, SALE_PRICE - 1),
+			Error::<Test>::BidTooLow
+		);
+
+		assert_ok!(KittiesModule::bid(RuntimeOrigin::signed(bidder_1), kitty_id, SALE_PRICE));
+		assert_eq!(Balances::reserved_balance(bidder_1), SALE_PRICE);
+
+		assert_noop!(
+			KittiesModule::bid(RuntimeOrigin::signed(bidder_2), kitty_id, SALE_PRICE),
+			Error::<Test>::BidTooLow
+		);
+
+		// 被超过的出价解除保留
+		assert_ok!(KittiesModule::bid(RuntimeOrigin::signed(bidder_2), kitty_id, SALE_PRICE * 2));
+		assert_eq!(Balances::reserved_balance(bidder_1), 0);
+		assert_eq!(Balances::reserved_balance(bidder_2), SALE_PRICE * 2);
+		System::assert_has_event(
+			Event::BidReleased { who: bidder_1, kitty_id, amount: SALE_PRICE }.into()
+		);
+
+		// 同一出价者加价只保留差额
+		assert_ok!(KittiesModule::bid(RuntimeOrigin::signed(bidder_2), kitty_id, SALE_PRICE * 3));
+		assert_eq!(Balances::reserved_balance(bidder_2), SALE_PRICE * 3);
+		System::assert_has_event(
+			Event::BidPlaced { who: bidder_2, kitty_id, amount: SALE_PRICE * 3 }.into()
+		);
+
+		run_to_block(end_block);
+		assert_noop!(
+			KittiesModule::bid(RuntimeOrigin::signed(bidder_1), kitty_id, SALE_PRICE * 4),
+			Error::<Test>::NotInAuction
+		);
+	});
+}
+
+#[test]
+fn it_works_for_settle_auction() {
+	new_test_ext().execute_with(|| {
+		let kitty_id: u32 = 0;
+		let seller: u64 = 1;
+		let bidder: u64 = 2;
+		let end_block: u64 = 10;
+
+		assert_ok!(KittiesModule::create(RuntimeOrigin::signed(seller), KITTY_NAME.to_vec()));
+		assert_ok!(KittiesModule::create(RuntimeOrigin::signed(seller), KITTY_NAME.to_vec()));
+		assert_ok!(
+			KittiesModule::create_auction(RuntimeOrigin::signed(seller), kitty_id, SALE_PRICE, end_block)
+		);
+		assert_ok!(
+			KittiesModule::create_auction(RuntimeOrigin::signed(seller), kitty_id + 1, SALE_PRICE, end_block)
+		);
+		assert_ok!(KittiesModule::bid(RuntimeOrigin::signed(bidder), kitty_id, SALE_PRICE));
+
+		let seller_balance = Balances::free_balance(seller);
+		let bidder_balance = Balances::free_balance(bidder) + Balances::reserved_balance(bidder);
+
+		run_to_block(end_block - 1);
+		assert_eq!(KittiesModule::kitty_owner(kitty_id), Some(seller));
+
+		run_to_block(end_block);
+		assert_eq!(KittiesModule::kitty_owner(kitty_id), Some(bidder));
+		assert_eq!(KittiesModule::kitties_of(&bidder), vec![kitty_id]);
+		let fee = MarketplaceFee::get() * SALE_PRICE;
+		assert_eq!(Balances::free_balance(seller), seller_balance + SALE_PRICE - fee);
+		assert_eq!(Balances::free_balance(bidder), bidder_balance - SALE_PRICE);
+		assert_eq!(Balances::reserved_balance(bidder), 0);
+		System::assert_has_event(
+			Event::AuctionSettled { kitty_id, winner: bidder, price: SALE_PRICE, fee, royalty: 0 }.into()
 		);
+
+		// 没有出价的拍卖流拍
+		assert_eq!(KittiesModule::kitty_owner(kitty_id + 1), Some(seller));
+		System::assert_has_event(Event::AuctionUnsold { kitty_id: kitty_id + 1 }.into());
+
+		assert_eq!(KittiesModule::auctions(kitty_id), None);
+		assert_eq!(KittiesModule::auctions_ending(end_block).len(), 0);
+		assert_ok!(KittiesModule::transfer(RuntimeOrigin::signed(seller), bidder, kitty_id + 1));
+	});
+}
+
+#[test]
+fn it_keeps_unpaid_auctions_unsold() {
+	use frame_support::traits::tokens::nonfungible::Mutate;
+
+	new_test_ext().execute_with(|| {
+		let kitty_id: u32 = 0;
+		// 卖家账户不存在, 无法从保留余额收款
+		let seller: u64 = 6;
+		let bidder: u64 = 2;
+		let end_block: u64 = 10;
+
+		assert_ok!(<KittiesModule as Mutate<u64>>::mint_into(&kitty_id, &seller));
+		assert_ok!(
+			KittiesModule::create_auction(RuntimeOrigin::signed(seller), kitty_id, SALE_PRICE, end_block)
+		);
+		assert_ok!(KittiesModule::bid(RuntimeOrigin::signed(bidder), kitty_id, SALE_PRICE));
+		let bidder_balance = Balances::free_balance(bidder) + Balances::reserved_balance(bidder);
+
+		// 付款失败时小猫留给卖家, 出价全部释放
+		run_to_block(end_block);
+		assert_eq!(KittiesModule::kitty_owner(kitty_id), Some(seller));
+		assert_eq!(KittiesModule::kitties_of(&bidder), Vec::<u32>::new());
+		assert_eq!(Balances::free_balance(bidder), bidder_balance);
+		assert_eq!(Balances::reserved_balance(bidder), 0);
+		assert_eq!(Balances::total_balance(&seller), 0);
+		System::assert_has_event(Event::AuctionUnsold { kitty_id }.into());
+	});
+}
+
+#[test]
+fn it_works_for_make_offer() {
+	new_test_ext().execute_with(|| {
+		let kitty_id: u32 = 0;
+		let owner: u64 = 1;
+		let offerer: u64 = 2;
+		let expiry: u64 = 10;
+
+		assert_noop!(
+			KittiesModule::make_offer(RuntimeOrigin::signed(offerer), kitty_id, SALE_PRICE, expiry),
+			Error::<Test>::InvalidKittyId
+		);
+
+		assert_ok!(KittiesModule::create(RuntimeOrigin::signed(owner), KITTY_NAME.to_vec()));
+
+		assert_noop!(
+			KittiesModule::make_offer(RuntimeOrigin::signed(owner), kitty_id, SALE_PRICE, expiry),
+			Error::<Test>::AlreadyOwned
+		);
+		assert_noop!(
+			KittiesModule::make_offer(RuntimeOrigin::signed(offerer), kitty_id, SALE_PRICE, 1),
+			Error::<Test>::InvalidOfferExpiry
+		);
+
+		// 未上架的 kitty 也可以出价
+		assert_ok!(KittiesModule::make_offer(RuntimeOrigin::signed(offerer), kitty_id, SALE_PRICE, expiry));
+		assert_eq!(Balances::reserved_balance(offerer), SALE_PRICE);
+		assert_eq!(KittiesModule::offers(kitty_id).len(), 1);
+		System::assert_has_event(
+			Event::OfferMade { who: offerer, kitty_id, amount: SALE_PRICE, expiry }.into()
+		);
+
+		assert_noop!(
+			KittiesModule::make_offer(RuntimeOrigin::signed(offerer), kitty_id, SALE_PRICE, expiry),
+			Error::<Test>::AlreadyOffered
+		);
+
+		// 每个 kitty 的出价数量有上限
+		let max_offers = <Test as crate::Config>::MaxOffersPerKitty::get() as u64;
+		for account_id in 3..(max_offers + 2) {
+			assert_ok!(
+				KittiesModule::make_offer(RuntimeOrigin::signed(account_id), kitty_id, SALE_PRICE, expiry)
+			);
+		}
+		assert_noop!(
+			KittiesModule::make_offer(RuntimeOrigin::signed(max_offers + 2), kitty_id, SALE_PRICE, expiry),
+			Error::<Test>::TooManyOffers
+		);
+	});
+}
+
+#[test]
+fn it_works_for_withdraw_offer() {
+	new_test_ext().execute_with(|| {
+		let kitty_id: u32 = 0;
+		let owner: u64 = 1;
+		let offerer: u64 = 2;
+
+		assert_ok!(KittiesModule::create(RuntimeOrigin::signed(owner), KITTY_NAME.to_vec()));
+
+		assert_noop!(
+			KittiesModule::withdraw_offer(RuntimeOrigin::signed(offerer), kitty_id),
+			Error::<Test>::NoOffer
+		);
+
+		assert_ok!(KittiesModule::make_offer(RuntimeOrigin::signed(offerer), kitty_id, SALE_PRICE, 10));
+		assert_ok!(KittiesModule::withdraw_offer(RuntimeOrigin::signed(offerer), kitty_id));
+		assert_eq!(Balances::reserved_balance(offerer), 0);
+		assert_eq!(KittiesModule::offers(kitty_id).len(), 0);
+		System::assert_has_event(
+			Event::OfferWithdrawn { who: offerer, kitty_id, amount: SALE_PRICE }.into()
+		);
+	});
+}
+
+#[test]
+fn it_works_for_accept_offer() {
+	new_test_ext().execute_with(|| {
+		let kitty_id: u32 = 0;
+		let owner: u64 = 1;
+		let offerer: u64 = 2;
+		let other_offerer: u64 = 3;
+		let expiry: u64 = 10;
+
+		assert_ok!(KittiesModule::create(RuntimeOrigin::signed(owner), KITTY_NAME.to_vec()));
+		assert_ok!(KittiesModule::sale(RuntimeOrigin::signed(owner), kitty_id, SALE_PRICE * 2, None));
+		assert_ok!(KittiesModule::make_offer(RuntimeOrigin::signed(offerer), kitty_id, SALE_PRICE, expiry));
+		assert_ok!(
+			KittiesModule::make_offer(RuntimeOrigin::signed(other_offerer), kitty_id, SALE_PRICE, expiry)
+		);
+
+		assert_noop!(
+			KittiesModule::accept_offer(RuntimeOrigin::signed(offerer), kitty_id, offerer),
+			Error::<Test>::NotOwner
+		);
+		assert_noop!(
+			KittiesModule::accept_offer(RuntimeOrigin::signed(owner), kitty_id, 4u64),
+			Error::<Test>::NoOffer
+		);
+
+		let owner_balance = Balances::free_balance(owner);
+
+		assert_ok!(KittiesModule::accept_offer(RuntimeOrigin::signed(owner), kitty_id, offerer));
+		assert_eq!(KittiesModule::kitty_owner(kitty_id), Some(offerer));
+		assert_eq!(KittiesModule::kitty_on_sale(kitty_id), None);
+		let fee = MarketplaceFee::get() * SALE_PRICE;
+		assert_eq!(Balances::free_balance(owner), owner_balance + SALE_PRICE - fee);
+		assert_eq!(Balances::reserved_balance(offerer), 0);
+		System::assert_has_event(
+			Event::OfferAccepted { who: owner, offerer, kitty_id, amount: SALE_PRICE, fee, royalty: 0 }
+				.into()
+		);
+
+		// 其他出价保留给新的主人
+		assert_eq!(KittiesModule::offers(kitty_id).len(), 1);
+
+		// 过期的出价不能接受
+		run_to_block(expiry);
+		assert_noop!(
+			KittiesModule::accept_offer(RuntimeOrigin::signed(offerer), kitty_id, other_offerer),
+			Error::<Test>::OfferExpired
+		);
+	});
+}
+
+#[test]
+fn it_fails_to_accept_underfunded_offer() {
+	use frame_support::traits::ReservableCurrency;
+
+	new_test_ext().execute_with(|| {
+		let kitty_id: u32 = 0;
+		let owner: u64 = 1;
+		let offerer: u64 = 2;
+
+		assert_ok!(KittiesModule::create(RuntimeOrigin::signed(owner), KITTY_NAME.to_vec()));
+		assert_ok!(KittiesModule::make_offer(RuntimeOrigin::signed(offerer), kitty_id, SALE_PRICE, 10));
+
+		// 预留被罚没一部分后, 出价不足以支付, 接受失败且不留下部分支付
+		let _ = Balances::slash_reserved(&offerer, 1_000);
+		assert_noop!(
+			KittiesModule::accept_offer(RuntimeOrigin::signed(owner), kitty_id, offerer),
+			Error::<Test>::InsufficientReserve
+		);
+	});
+}
+
+#[test]
+fn it_works_for_expired_offer_cleanup() {
+	new_test_ext().execute_with(|| {
+		let kitty_id: u32 = 0;
+		let owner: u64 = 1;
+		let offerer: u64 = 2;
+
+		assert_ok!(KittiesModule::create(RuntimeOrigin::signed(owner), KITTY_NAME.to_vec()));
+		assert_ok!(KittiesModule::make_offer(RuntimeOrigin::signed(offerer), kitty_id, SALE_PRICE, 5));
+
+		run_to_block(5);
+
+		// 新的出价会清理掉过期的出价
+		assert_ok!(KittiesModule::make_offer(RuntimeOrigin::signed(3u64), kitty_id, SALE_PRICE, 10));
+		assert_eq!(Balances::reserved_balance(offerer), 0);
+		assert_eq!(KittiesModule::offers(kitty_id).len(), 1);
+		System::assert_has_event(
+			Event::OfferExpired { who: offerer, kitty_id, amount: SALE_PRICE }.into()
+		);
+	});
+}
+
+#[test]
+fn it_releases_expired_offers_on_withdraw_and_accept() {
+	new_test_ext().execute_with(|| {
+		let owner: u64 = 1;
+		let offerer: u64 = 2;
+
+		assert_ok!(KittiesModule::create(RuntimeOrigin::signed(owner), KITTY_NAME.to_vec()));
+		assert_ok!(KittiesModule::create(RuntimeOrigin::signed(owner), KITTY_NAME.to_vec()));
+		assert_ok!(KittiesModule::make_offer(RuntimeOrigin::signed(offerer), 0, SALE_PRICE, 5));
+		assert_ok!(KittiesModule::make_offer(RuntimeOrigin::signed(3u64), 0, SALE_PRICE, 10));
+		assert_ok!(KittiesModule::make_offer(RuntimeOrigin::signed(offerer), 1, SALE_PRICE, 5));
+		assert_ok!(KittiesModule::make_offer(RuntimeOrigin::signed(3u64), 1, SALE_PRICE, 10));
+
+		run_to_block(5);
+
+		// 撤回出价时释放其他过期的出价
+		assert_ok!(KittiesModule::withdraw_offer(RuntimeOrigin::signed(3u64), 0));
+		assert_eq!(Balances::reserved_balance(offerer), SALE_PRICE);
+		assert!(KittiesModule::offers(0).is_empty());
+		System::assert_has_event(Event::OfferExpired { who: offerer, kitty_id: 0, amount: SALE_PRICE }.into());
+
+		// 接受出价时也释放过期的出价
+		assert_ok!(KittiesModule::accept_offer(RuntimeOrigin::signed(owner), 1, 3u64));
+		assert_eq!(Balances::reserved_balance(offerer), 0);
+		assert!(KittiesModule::offers(1).is_empty());
+		System::assert_has_event(Event::OfferExpired { who: offerer, kitty_id: 1, amount: SALE_PRICE }.into());
+	});
+}
+
+#[test]
+fn it_works_for_cancel_sale() {
+	new_test_ext().execute_with(|| {
+		let kitty_id: u32 = 0;
+		let account_id: u64 = 1;
+
+		assert_ok!(KittiesModule::create(RuntimeOrigin::signed(account_id), KITTY_NAME.to_vec()));
+
+		assert_noop!(
+			KittiesModule::cancel_sale(RuntimeOrigin::signed(account_id), kitty_id),
+			Error::<Test>::NotOnSale
+		);
+
+		assert_ok!(KittiesModule::sale(RuntimeOrigin::signed(account_id), kitty_id, SALE_PRICE, Some(10)));
+
+		assert_noop!(
+			KittiesModule::cancel_sale(RuntimeOrigin::signed(2u64), kitty_id),
+			Error::<Test>::NotOwner
+		);
+
+		assert_ok!(KittiesModule::cancel_sale(RuntimeOrigin::signed(account_id), kitty_id));
+		assert_eq!(KittiesModule::kitty_on_sale(kitty_id), None);
+		assert_eq!(KittiesModule::sale_expiry(kitty_id), None);
+		assert_eq!(KittiesModule::sales_expiring(10).len(), 0);
+		System::assert_has_event(Event::KittySaleCancelled { who: account_id, kitty_id }.into());
+
+		// 下架后可以重新上架
+		assert_ok!(KittiesModule::sale(RuntimeOrigin::signed(account_id), kitty_id, SALE_PRICE, None));
+	});
+}
+
+#[test]
+fn it_works_for_sale_expiry() {
+	new_test_ext().execute_with(|| {
+		let kitty_id: u32 = 0;
+		let account_id: u64 = 1;
+		let expiry: u64 = 5;
+
+		assert_ok!(KittiesModule::create(RuntimeOrigin::signed(account_id), KITTY_NAME.to_vec()));
+
+		assert_noop!(
+			KittiesModule::sale(RuntimeOrigin::signed(account_id), kitty_id, SALE_PRICE, Some(1)),
+			Error::<Test>::InvalidSaleExpiry
+		);
+
+		assert_ok!(
+			KittiesModule::sale(RuntimeOrigin::signed(account_id), kitty_id, SALE_PRICE, Some(expiry))
+		);
+		assert_eq!(KittiesModule::sale_expiry(kitty_id), Some(expiry));
+
+		run_to_block(expiry - 1);
+		assert_eq!(KittiesModule::kitty_on_sale(kitty_id), Some(SALE_PRICE));
+
+		run_to_block(expiry);
+		assert_eq!(KittiesModule::kitty_on_sale(kitty_id), None);
+		assert_eq!(KittiesModule::sale_expiry(kitty_id), None);
+		System::assert_has_event(Event::KittySaleExpired { kitty_id }.into());
+
+		assert_noop!(
+			KittiesModule::buy(RuntimeOrigin::signed(2u64), kitty_id, SALE_PRICE),
+			Error::<Test>::NotOnSale
+		);
+	});
+}
+
+#[test]
+fn it_works_for_delist_on_transfer() {
+	new_test_ext().execute_with(|| {
+		let kitty_id: u32 = 0;
+		let account_id: u64 = 1;
+		let to_account_id: u64 = 2;
+
+		assert_ok!(KittiesModule::create(RuntimeOrigin::signed(account_id), KITTY_NAME.to_vec()));
+		assert_ok!(KittiesModule::sale(RuntimeOrigin::signed(account_id), kitty_id, SALE_PRICE, Some(10)));
+
+		assert_ok!(KittiesModule::transfer(RuntimeOrigin::signed(account_id), to_account_id, kitty_id));
+		assert_eq!(KittiesModule::kitty_on_sale(kitty_id), None);
+		assert_eq!(KittiesModule::sales_expiring(10).len(), 0);
+		System::assert_has_event(Event::KittyDelisted { kitty_id }.into());
+
+		// 新主人可以重新上架
+		assert_ok!(KittiesModule::sale(RuntimeOrigin::signed(to_account_id), kitty_id, SALE_PRICE, None));
+	});
+}
+
+#[test]
+fn it_works_for_burn() {
+	new_test_ext().execute_with(|| {
+		let kitty_id: u32 = 0;
+		let account_id: u64 = 1;
+		let offerer: u64 = 2;
+
+		assert_ok!(KittiesModule::create(RuntimeOrigin::signed(account_id), KITTY_NAME.to_vec()));
+		assert_ok!(KittiesModule::create(RuntimeOrigin::signed(account_id), KITTY_NAME.to_vec()));
+		assert_ok!(
+			KittiesModule::breed(RuntimeOrigin::signed(account_id), kitty_id, kitty_id + 1, KITTY_NAME.to_vec())
+		);
+		assert_ok!(KittiesModule::sale(RuntimeOrigin::signed(account_id), kitty_id + 2, SALE_PRICE, Some(10)));
+		assert_ok!(KittiesModule::make_offer(RuntimeOrigin::signed(offerer), kitty_id + 2, SALE_PRICE, 10));
+
+		assert_noop!(
+			KittiesModule::burn(RuntimeOrigin::signed(offerer), kitty_id + 2),
+			Error::<Test>::NotOwner
+		);
+
+		assert_eq!(KittiesModule::mint_price(kitty_id + 2), Some(KittyPrice::get()));
+		let balance = Balances::free_balance(account_id);
+		let refund = BurnRefund::get() * KittyPrice::get();
+		let deposit = NameDepositPerByte::get() * KITTY_NAME.len() as Balance;
+
+		// 退还部分铸造费用和名字押金
+		assert_ok!(KittiesModule::burn(RuntimeOrigin::signed(account_id), kitty_id + 2));
+		assert_eq!(Balances::free_balance(account_id), balance + refund + deposit);
+		assert_eq!(KittiesModule::name_deposit(kitty_id + 2), None);
+		assert_eq!(Balances::reserved_balance(offerer), 0);
+		assert_eq!(KittiesModule::kitties(kitty_id + 2), None);
+		assert_eq!(KittiesModule::kitty_owner(kitty_id + 2), None);
+		assert_eq!(KittiesModule::kitty_on_sale(kitty_id + 2), None);
+		assert_eq!(KittiesModule::kitty_parents(kitty_id + 2), None);
+		assert_eq!(KittiesModule::sales_expiring(10).len(), 0);
+		assert_eq!(KittiesModule::kitties_of(&account_id), vec![kitty_id, kitty_id + 1]);
+		System::assert_has_event(
+			Event::KittyBurned { who: account_id, kitty_id: kitty_id + 2, refund }.into()
+		);
+
+		assert_noop!(
+			KittiesModule::burn(RuntimeOrigin::signed(account_id), kitty_id + 2),
+			Error::<Test>::NotOwner
+		);
+	});
+}
+
+#[test]
+fn it_keeps_lineage_after_burn() {
+	new_test_ext().execute_with(|| {
+		let kitty_id: u32 = 0;
+		let account_id: u64 = 1;
+
+		assert_ok!(KittiesModule::create(RuntimeOrigin::signed(account_id), KITTY_NAME.to_vec()));
+		assert_ok!(KittiesModule::create(RuntimeOrigin::signed(account_id), KITTY_NAME.to_vec()));
+		assert_ok!(
+			KittiesModule::breed(RuntimeOrigin::signed(account_id), kitty_id, kitty_id + 1, KITTY_NAME.to_vec())
+		);
+
+		// 烧掉父代后子代的血统不变
+		assert_ok!(KittiesModule::burn(RuntimeOrigin::signed(account_id), kitty_id));
+		assert_eq!(KittiesModule::kitty_parents(kitty_id + 2), Some((kitty_id, kitty_id + 1)));
+		assert_eq!(KittiesModule::kitty_generation(kitty_id + 2), 1);
+
+		assert_noop!(
+			KittiesModule::breed(RuntimeOrigin::signed(account_id), kitty_id, kitty_id + 1, KITTY_NAME.to_vec()),
+			Error::<Test>::InvalidKittyId
+		);
+	});
+}
+
+#[test]
+fn it_works_for_rename() {
+	new_test_ext().execute_with(|| {
+		let kitty_id: u32 = 0;
+		let account_id: u64 = 1;
+		let other_account_id: u64 = 2;
+		let max_length = <Test as crate::Config>::MaxNameLength::get() as usize;
+
+		// 名字按字节收取押金
+		assert_ok!(KittiesModule::create(RuntimeOrigin::signed(account_id), KITTY_NAME.to_vec()));
+		let deposit = NameDepositPerByte::get() * KITTY_NAME.len() as Balance;
+		assert_eq!(Balances::reserved_balance(account_id), deposit);
+		assert_eq!(KittiesModule::name_deposit(kitty_id), Some((account_id, deposit)));
+
+		assert_noop!(
+			KittiesModule::create(RuntimeOrigin::signed(account_id), vec![b'a'; max_length + 1]),
+			Error::<Test>::NameTooLong
+		);
+		assert_noop!(
+			KittiesModule::rename(RuntimeOrigin::signed(other_account_id), kitty_id, b"xyz".to_vec()),
+			Error::<Test>::NotOwner
+		);
+		assert_noop!(
+			KittiesModule::rename(RuntimeOrigin::signed(account_id), kitty_id, vec![b'a'; max_length + 1]),
+			Error::<Test>::NameTooLong
+		);
+		assert_noop!(
+			KittiesModule::rename(RuntimeOrigin::signed(account_id), kitty_id + 1, b"xyz".to_vec()),
+			Error::<Test>::InvalidKittyId
+		);
+
+		// 转移后由新主人改名, 旧押金退还给原主人
+		assert_ok!(
+			KittiesModule::transfer(RuntimeOrigin::signed(account_id), other_account_id, kitty_id)
+		);
+		let name = b"mittens".to_vec();
+		assert_ok!(KittiesModule::rename(RuntimeOrigin::signed(other_account_id), kitty_id, name.clone()));
+
+		let deposit = NameDepositPerByte::get() * name.len() as Balance;
+		assert_eq!(Balances::reserved_balance(account_id), 0);
+		assert_eq!(Balances::reserved_balance(other_account_id), deposit);
+		assert_eq!(KittiesModule::name_deposit(kitty_id), Some((other_account_id, deposit)));
+		assert_eq!(KittiesModule::kitties(kitty_id).unwrap().name.into_inner(), name);
+		System::assert_has_event(
+			Event::KittyRenamed {
+				who: other_account_id,
+				kitty_id,
+				name: name.try_into().unwrap(),
+			}
+			.into()
+		);
+	});
+}
+
+#[test]
+fn it_works_for_approve() {
+	new_test_ext().execute_with(|| {
+		let kitty_id: u32 = 0;
+		let owner: u64 = 1;
+		let delegate: u64 = 2;
+		let to: u64 = 3;
+
+		assert_ok!(KittiesModule::create(RuntimeOrigin::signed(owner), KITTY_NAME.to_vec()));
+
+		assert_noop!(
+			KittiesModule::approve(RuntimeOrigin::signed(delegate), kitty_id, Some(delegate)),
+			Error::<Test>::NotApproved
+		);
+		assert_noop!(
+			KittiesModule::transfer_from(RuntimeOrigin::signed(delegate), owner, to, kitty_id),
+			Error::<Test>::NotApproved
+		);
+
+		assert_ok!(KittiesModule::approve(RuntimeOrigin::signed(owner), kitty_id, Some(delegate)));
+		assert_eq!(KittiesModule::kitty_approval(kitty_id), Some(delegate));
+		System::assert_has_event(
+			Event::Approval { owner, delegate: Some(delegate), kitty_id }.into()
+		);
+
+		assert_noop!(
+			KittiesModule::transfer_from(RuntimeOrigin::signed(delegate), to, delegate, kitty_id),
+			Error::<Test>::NotOwner
+		);
+		assert_ok!(KittiesModule::transfer_from(RuntimeOrigin::signed(delegate), owner, to, kitty_id));
+		assert_eq!(KittiesModule::kitty_owner(kitty_id), Some(to));
+		System::assert_has_event(Event::KittyTransferred { from: owner, to, kitty_id }.into());
+
+		// 所有权变更后授权被清除
+		assert_eq!(KittiesModule::kitty_approval(kitty_id), None);
+		assert_noop!(
+			KittiesModule::transfer_from(RuntimeOrigin::signed(delegate), to, owner, kitty_id),
+			Error::<Test>::NotApproved
+		);
+
+		// 取消授权
+		assert_ok!(KittiesModule::approve(RuntimeOrigin::signed(to), kitty_id, Some(delegate)));
+		assert_ok!(KittiesModule::approve(RuntimeOrigin::signed(to), kitty_id, None));
+		assert_eq!(KittiesModule::kitty_approval(kitty_id), None);
+	});
+}
+
+#[test]
+fn it_works_for_set_approval_for_all() {
+	new_test_ext().execute_with(|| {
+		let kitty_id: u32 = 0;
+		let owner: u64 = 1;
+		let operator: u64 = 2;
+		let delegate: u64 = 3;
+		let buyer: u64 = 4;
+
+		assert_ok!(KittiesModule::create(RuntimeOrigin::signed(owner), KITTY_NAME.to_vec()));
+		assert_ok!(KittiesModule::create(RuntimeOrigin::signed(owner), KITTY_NAME.to_vec()));
+
+		assert_ok!(KittiesModule::set_approval_for_all(RuntimeOrigin::signed(owner), operator, true));
+		System::assert_has_event(
+			Event::ApprovalForAll { owner, operator, approved: true }.into()
+		);
+
+		// operator 可以替主人授权和转移任意 kitty
+		assert_ok!(KittiesModule::approve(RuntimeOrigin::signed(operator), kitty_id, Some(delegate)));
+		assert_eq!(KittiesModule::kitty_approval(kitty_id), Some(delegate));
+		assert_ok!(
+			KittiesModule::transfer_from(RuntimeOrigin::signed(operator), owner, operator, kitty_id + 1)
+		);
+		assert_eq!(KittiesModule::kitty_owner(kitty_id + 1), Some(operator));
+
+		// 通过 buy 变更所有权也会清除授权
+		assert_ok!(KittiesModule::sale(RuntimeOrigin::signed(owner), kitty_id, SALE_PRICE, None));
+		assert_ok!(KittiesModule::buy(RuntimeOrigin::signed(buyer), kitty_id, SALE_PRICE));
+		assert_eq!(KittiesModule::kitty_approval(kitty_id), None);
+
+		assert_ok!(KittiesModule::set_approval_for_all(RuntimeOrigin::signed(buyer), operator, false));
+		assert_noop!(
+			KittiesModule::transfer_from(RuntimeOrigin::signed(operator), buyer, operator, kitty_id),
+			Error::<Test>::NotApproved
+		);
+	});
+}
+
+#[test]
+fn it_works_for_nonfungible_traits() {
+	use frame_support::traits::tokens::nonfungible::{Inspect, Mutate, Transfer};
+
+	new_test_ext().execute_with(|| {
+		let kitty_id: u32 = 0;
+		let account_id: u64 = 1;
+		let other_account_id: u64 = 2;
+
+		// 只能按顺序铸造
+		assert_noop!(
+			<KittiesModule as Mutate<u64>>::mint_into(&(kitty_id + 1), &account_id),
+			Error::<Test>::InvalidKittyId
+		);
+		assert_ok!(<KittiesModule as Mutate<u64>>::mint_into(&kitty_id, &account_id));
+		assert_eq!(<KittiesModule as Inspect<u64>>::owner(&kitty_id), Some(account_id));
+		assert_eq!(KittiesModule::mint_price(kitty_id), Some(0));
+		assert!(<KittiesModule as Inspect<u64>>::can_transfer(&kitty_id));
+
+		let kitty = KittiesModule::kitties(kitty_id).unwrap();
+		assert_eq!(
+			<KittiesModule as Inspect<u64>>::attribute(&kitty_id, b"dna"),
+			Some(kitty.dna.to_vec())
+		);
+		assert_eq!(<KittiesModule as Inspect<u64>>::attribute(&kitty_id, b"name"), Some(vec![]));
+		assert_eq!(<KittiesModule as Inspect<u64>>::attribute(&kitty_id, b"color"), None);
+
+		// 通过属性改名, 押金由主人支付
+		assert_ok!(<KittiesModule as Mutate<u64>>::set_attribute(&kitty_id, b"name", KITTY_NAME));
+		assert_eq!(
+			<KittiesModule as Inspect<u64>>::attribute(&kitty_id, b"name"),
+			Some(KITTY_NAME.to_vec())
+		);
+		assert_eq!(
+			Balances::reserved_balance(account_id),
+			NameDepositPerByte::get() * KITTY_NAME.len() as Balance
+		);
+		assert_noop!(
+			<KittiesModule as Mutate<u64>>::set_attribute(&kitty_id, b"dna", &[0u8; 16]),
+			sp_runtime::TokenError::Unsupported
+		);
+
+		assert_ok!(<KittiesModule as Transfer<u64>>::transfer(&kitty_id, &other_account_id));
+		assert_eq!(<KittiesModule as Inspect<u64>>::owner(&kitty_id), Some(other_account_id));
+		assert_eq!(KittiesModule::kitties_of(&other_account_id), vec![kitty_id]);
+
+		assert_noop!(
+			<KittiesModule as Mutate<u64>>::burn(&kitty_id, Some(&account_id)),
+			Error::<Test>::NotOwner
+		);
+		assert_ok!(<KittiesModule as Mutate<u64>>::burn(&kitty_id, Some(&other_account_id)));
+		assert_eq!(<KittiesModule as Inspect<u64>>::owner(&kitty_id), None);
+		assert!(!<KittiesModule as Inspect<u64>>::can_transfer(&kitty_id));
+		assert_eq!(Balances::reserved_balance(account_id), 0);
+	});
+}
+
+#[test]
+fn it_respects_auctions_in_nonfungible_traits() {
+	use frame_support::traits::tokens::nonfungible::{Inspect, Mutate, Transfer};
+
+	new_test_ext().execute_with(|| {
+		let kitty_id: u32 = 0;
+		let account_id: u64 = 1;
+
+		assert_ok!(<KittiesModule as Mutate<u64>>::mint_into(&kitty_id, &account_id));
+		assert_ok!(
+			KittiesModule::create_auction(RuntimeOrigin::signed(account_id), kitty_id, SALE_PRICE, 10)
+		);
+
+		assert!(!<KittiesModule as Inspect<u64>>::can_transfer(&kitty_id));
+		assert_noop!(
+			<KittiesModule as Transfer<u64>>::transfer(&kitty_id, &2),
+			Error::<Test>::KittyInAuction
+		);
+		assert_noop!(
+			<KittiesModule as Mutate<u64>>::burn(&kitty_id, None),
+			Error::<Test>::KittyInAuction
+		);
+	});
+}
+
+#[test]
+fn it_rolls_back_failed_nonfungible_calls() {
+	use frame_support::traits::{
+		tokens::nonfungible::{Mutate, Transfer},
+		Currency,
+	};
+
+	new_test_ext().execute_with(|| {
+		let account_id: u64 = 1;
+		let max_owned: u32 = <Test as crate::Config>::MaxKittiesOwned::get();
+
+		for kitty_id in 0..max_owned {
+			assert_ok!(<KittiesModule as Mutate<u64>>::mint_into(&kitty_id, &2));
+		}
+		assert_ok!(<KittiesModule as Mutate<u64>>::mint_into(&max_owned, &account_id));
+
+		// 拥有数量已满时铸造和转移都不留下部分写入
+		assert_noop!(
+			<KittiesModule as Mutate<u64>>::mint_into(&(max_owned + 1), &2),
+			Error::<Test>::TooManyKitties
+		);
+		assert_noop!(
+			<KittiesModule as Transfer<u64>>::transfer(&max_owned, &2),
+			Error::<Test>::TooManyKitties
+		);
+
+		// 押金不足时改名不会丢掉旧名字的押金
+		assert_ok!(<KittiesModule as Mutate<u64>>::set_attribute(&max_owned, b"name", KITTY_NAME));
+		Balances::make_free_balance_be(&account_id, EXISTENTIAL_DEPOSIT);
+		assert_noop!(
+			<KittiesModule as Mutate<u64>>::set_attribute(&max_owned, b"name", b"abcdefgh"),
+			pallet_balances::Error::<Test>::InsufficientBalance
+		);
+	});
+}
+
+#[test]
+fn it_works_for_rent() {
+	new_test_ext().execute_with(|| {
+		let kitty_id: u32 = 0;
+		let owner: u64 = 1;
+		let renter: u64 = 2;
+		let price_per_block: Balance = 100;
+
+		assert_ok!(KittiesModule::create(RuntimeOrigin::signed(owner), KITTY_NAME.to_vec()));
+		assert_noop!(
+			KittiesModule::rent(RuntimeOrigin::signed(renter), kitty_id, 5),
+			Error::<Test>::NoRentalOffer
+		);
+		assert_noop!(
+			KittiesModule::offer_rental(RuntimeOrigin::signed(renter), kitty_id, price_per_block, 10),
+			Error::<Test>::NotOwner
+		);
+		assert_noop!(
+			KittiesModule::offer_rental(RuntimeOrigin::signed(owner), kitty_id, price_per_block, 0),
+			Error::<Test>::InvalidRentalDuration
+		);
+
+		assert_ok!(KittiesModule::offer_rental(RuntimeOrigin::signed(owner), kitty_id, price_per_block, 10));
+		System::assert_has_event(
+			Event::RentalOffered { who: owner, kitty_id, price_per_block, max_duration: 10 }.into()
+		);
+
+		assert_noop!(
+			KittiesModule::rent(RuntimeOrigin::signed(owner), kitty_id, 5),
+			Error::<Test>::AlreadyOwned
+		);
+		assert_noop!(
+			KittiesModule::rent(RuntimeOrigin::signed(renter), kitty_id, 11),
+			Error::<Test>::InvalidRentalDuration
+		);
+		assert_noop!(
+			KittiesModule::rent(RuntimeOrigin::signed(renter), kitty_id, 0),
+			Error::<Test>::InvalidRentalDuration
+		);
+
+		// 租金一次性付给主人
+		let owner_balance = Balances::free_balance(owner);
+		let renter_balance = Balances::free_balance(renter);
+		let end_block = System::block_number() + 5;
+		assert_ok!(KittiesModule::rent(RuntimeOrigin::signed(renter), kitty_id, 5));
+		assert_eq!(Balances::free_balance(owner), owner_balance + price_per_block * 5);
+		assert_eq!(Balances::free_balance(renter), renter_balance - price_per_block * 5);
+		assert_eq!(KittiesModule::kitty_user(kitty_id), Some(renter));
+		assert_eq!(KittiesModule::kitty_owner(kitty_id), Some(owner));
+		System::assert_has_event(
+			Event::KittyRented { who: renter, kitty_id, end_block, cost: price_per_block * 5 }.into()
+		);
+
+		assert_noop!(
+			KittiesModule::rent(RuntimeOrigin::signed(3u64), kitty_id, 5),
+			Error::<Test>::KittyRented
+		);
+
+		// 到期后归还使用权
+		run_to_block(end_block);
+		assert_eq!(KittiesModule::rental(kitty_id), None);
+		assert_eq!(KittiesModule::kitty_user(kitty_id), Some(owner));
+		System::assert_has_event(Event::RentalEnded { renter, kitty_id }.into());
+
+		// 转移后租赁报价失效
+		assert_ok!(KittiesModule::transfer(RuntimeOrigin::signed(owner), renter, kitty_id));
+		assert_eq!(KittiesModule::rental_offer(kitty_id), None);
+	});
+}
+
+#[test]
+fn it_respects_active_rentals() {
+	new_test_ext().execute_with(|| {
+		let kitty_id: u32 = 0;
+		let owner: u64 = 1;
+		let renter: u64 = 2;
+
+		assert_ok!(KittiesModule::create(RuntimeOrigin::signed(owner), KITTY_NAME.to_vec()));
+		assert_ok!(KittiesModule::create(RuntimeOrigin::signed(owner), KITTY_NAME.to_vec()));
+		assert_ok!(KittiesModule::sale(RuntimeOrigin::signed(owner), kitty_id, SALE_PRICE, None));
+		assert_ok!(KittiesModule::offer_rental(RuntimeOrigin::signed(owner), kitty_id, 100, 10));
+		assert_ok!(KittiesModule::offer_rental(RuntimeOrigin::signed(owner), kitty_id + 1, 100, 10));
+
+		// 在售的小猫不能出租, 否则买家会买到正在出租的小猫
+		assert_noop!(
+			KittiesModule::rent(RuntimeOrigin::signed(renter), kitty_id, 5),
+			Error::<Test>::AlreadyOnSale
+		);
+		assert_ok!(KittiesModule::cancel_sale(RuntimeOrigin::signed(owner), kitty_id));
+		assert_ok!(KittiesModule::rent(RuntimeOrigin::signed(renter), kitty_id, 5));
+
+		assert_noop!(
+			KittiesModule::transfer(RuntimeOrigin::signed(owner), 3u64, kitty_id),
+			Error::<Test>::KittyRented
+		);
+		assert_noop!(
+			KittiesModule::sale(RuntimeOrigin::signed(owner), kitty_id, SALE_PRICE, None),
+			Error::<Test>::KittyRented
+		);
+		assert_noop!(
+			KittiesModule::create_auction(RuntimeOrigin::signed(owner), kitty_id, SALE_PRICE, 10),
+			Error::<Test>::KittyRented
+		);
+		assert_noop!(
+			KittiesModule::burn(RuntimeOrigin::signed(owner), kitty_id),
+			Error::<Test>::KittyRented
+		);
+
+		// 租期内只有租客可以用来繁殖
+		assert_noop!(
+			KittiesModule::breed(RuntimeOrigin::signed(owner), kitty_id, kitty_id + 1, KITTY_NAME.to_vec()),
+			Error::<Test>::NotKittyUser
+		);
+		assert_noop!(
+			KittiesModule::breed(RuntimeOrigin::signed(renter), kitty_id, kitty_id + 1, KITTY_NAME.to_vec()),
+			Error::<Test>::NotOwner
+		);
+		assert_ok!(KittiesModule::rent(RuntimeOrigin::signed(renter), kitty_id + 1, 5));
+		assert_ok!(
+			KittiesModule::breed(RuntimeOrigin::signed(renter), kitty_id, kitty_id + 1, KITTY_NAME.to_vec())
+		);
+		assert_eq!(KittiesModule::kitty_owner(kitty_id + 2), Some(renter));
+	});
+}
+
+#[test]
+fn it_works_for_breeding_cooldown() {
+	new_test_ext().execute_with(|| {
+		let kitty_id: u32 = 0;
+		let account_id: u64 = 1;
+
+		// 冷却时间随代数增长, 且有上限
+		assert_eq!(KittiesModule::breeding_cooldown(0), 5);
+		assert_eq!(KittiesModule::breeding_cooldown(1), 10);
+		assert_eq!(KittiesModule::breeding_cooldown(10), 20);
+
+		assert_ok!(KittiesModule::create(RuntimeOrigin::signed(account_id), KITTY_NAME.to_vec()));
+		assert_ok!(KittiesModule::create(RuntimeOrigin::signed(account_id), KITTY_NAME.to_vec()));
+		assert_eq!(KittiesModule::breeding_cooldown_remaining(kitty_id), 0);
+
+		assert_ok!(
+			KittiesModule::breed(RuntimeOrigin::signed(account_id), kitty_id, kitty_id + 1, KITTY_NAME.to_vec())
+		);
+		assert_eq!(KittiesModule::last_bred(kitty_id), Some(1));
+		assert_eq!(KittiesModule::breeding_cooldown_remaining(kitty_id), 5);
+
+		// 同一区块内不能再次繁殖
+		assert_noop!(
+			KittiesModule::breed(RuntimeOrigin::signed(account_id), kitty_id, kitty_id + 1, KITTY_NAME.to_vec()),
+			Error::<Test>::BreedingCooldown
+		);
+
+		run_to_block(5);
+		assert_eq!(KittiesModule::breeding_cooldown_remaining(kitty_id), 1);
+		assert_noop!(
+			KittiesModule::breed(RuntimeOrigin::signed(account_id), kitty_id, kitty_id + 1, KITTY_NAME.to_vec()),
+			Error::<Test>::BreedingCooldown
+		);
+
+		run_to_block(6);
+		assert_ok!(
+			KittiesModule::breed(RuntimeOrigin::signed(account_id), kitty_id, kitty_id + 2, KITTY_NAME.to_vec())
+		);
+
+		// 第一代的冷却更长
+		assert_eq!(KittiesModule::breeding_cooldown_remaining(kitty_id + 2), 10);
+	});
+}
+
+#[test]
+fn it_fails_for_breed_without_ownership() {
+	new_test_ext().execute_with(|| {
+		let kitty_id: u32 = 0;
+		let account_id: u64 = 1;
+		let other_account_id: u64 = 2;
+
+		assert_ok!(KittiesModule::create(RuntimeOrigin::signed(account_id), KITTY_NAME.to_vec()));
+		assert_ok!(KittiesModule::create(RuntimeOrigin::signed(other_account_id), KITTY_NAME.to_vec()));
+
+		assert_noop!(
+			KittiesModule::breed(RuntimeOrigin::signed(account_id), kitty_id, kitty_id + 1, KITTY_NAME.to_vec()),
+			Error::<Test>::NotOwner
+		);
+		assert_noop!(
+			KittiesModule::breed(RuntimeOrigin::signed(3u64), kitty_id, kitty_id + 1, KITTY_NAME.to_vec()),
+			Error::<Test>::NotOwner
+		);
+	});
+}
+
+#[test]
+fn it_works_for_breed_with_sire() {
+	new_test_ext().execute_with(|| {
+		let matron_id: u32 = 0;
+		let sire_id: u32 = 1;
+		let account_id: u64 = 1;
+		let sire_owner: u64 = 2;
+		let fee: Balance = 1_000;
+
+		assert_ok!(KittiesModule::create(RuntimeOrigin::signed(account_id), KITTY_NAME.to_vec()));
+		assert_ok!(KittiesModule::create(RuntimeOrigin::signed(sire_owner), KITTY_NAME.to_vec()));
+
+		assert_noop!(
+			KittiesModule::breed_with_sire(
+				RuntimeOrigin::signed(account_id), matron_id, sire_id, fee, KITTY_NAME.to_vec()
+			),
+			Error::<Test>::NotForSiring
+		);
+		assert_noop!(
+			KittiesModule::offer_siring(RuntimeOrigin::signed(account_id), sire_id, fee),
+			Error::<Test>::NotOwner
+		);
+
+		assert_ok!(KittiesModule::offer_siring(RuntimeOrigin::signed(sire_owner), sire_id, fee));
+		assert_eq!(KittiesModule::siring_offer(sire_id), Some(fee));
+		System::assert_has_event(Event::SiringOffered { who: sire_owner, kitty_id: sire_id, fee }.into());
+
+		assert_noop!(
+			KittiesModule::breed_with_sire(
+				RuntimeOrigin::signed(account_id), matron_id, sire_id, fee - 1, KITTY_NAME.to_vec()
+			),
+			Error::<Test>::PriceTooHigh
+		);
+		// 母本必须属于调用者
+		assert_noop!(
+			KittiesModule::breed_with_sire(
+				RuntimeOrigin::signed(3u64), matron_id, sire_id, fee, KITTY_NAME.to_vec()
+			),
+			Error::<Test>::NotOwner
+		);
+
+		// 配种费付给种公主人
+		let sire_owner_balance = Balances::free_balance(sire_owner);
+		assert_ok!(KittiesModule::breed_with_sire(
+			RuntimeOrigin::signed(account_id), matron_id, sire_id, fee, KITTY_NAME.to_vec()
+		));
+		let kitty_id = 2;
+		assert_eq!(Balances::free_balance(sire_owner), sire_owner_balance + fee);
+		assert_eq!(KittiesModule::kitty_owner(kitty_id), Some(account_id));
+		assert_eq!(KittiesModule::kitty_parents(kitty_id), Some((matron_id, sire_id)));
+		System::assert_has_event(
+			Event::KittySired { who: account_id, kitty_id, matron_id, sire_id, sire_owner, fee }.into()
+		);
+
+		// 种公也要冷却
+		assert_noop!(
+			KittiesModule::breed_with_sire(
+				RuntimeOrigin::signed(account_id), kitty_id, sire_id, fee, KITTY_NAME.to_vec()
+			),
+			Error::<Test>::BreedingCooldown
+		);
+
+		// 转移后配种报价失效
+		assert_ok!(KittiesModule::transfer(RuntimeOrigin::signed(sire_owner), 3u64, sire_id));
+		assert_eq!(KittiesModule::siring_offer(sire_id), None);
+
+		assert_ok!(KittiesModule::offer_siring(RuntimeOrigin::signed(3u64), sire_id, fee));
+		assert_ok!(KittiesModule::cancel_siring_offer(RuntimeOrigin::signed(3u64), sire_id));
+		assert_noop!(
+			KittiesModule::cancel_siring_offer(RuntimeOrigin::signed(3u64), sire_id),
+			Error::<Test>::NotForSiring
+		);
+	});
+}
+
+#[test]
+fn it_works_for_marketplace_fee_and_royalty() {
+	new_test_ext().execute_with(|| {
+		let kitty_id: u32 = 0;
+		let creator: u64 = 1;
+		let collector: u64 = 2;
+		let buyer: u64 = 3;
+		let fee = MarketplaceFee::get() * SALE_PRICE;
+		let royalty = CreatorRoyalty::get() * SALE_PRICE;
+
+		assert_ok!(KittiesModule::create(RuntimeOrigin::signed(creator), KITTY_NAME.to_vec()));
+		assert_eq!(KittiesModule::kitty_creator(kitty_id), Some(creator));
+		assert_eq!(KittiesModule::sale_breakdown(kitty_id, &creator, SALE_PRICE, false), (fee, None));
+		assert_ok!(KittiesModule::sale(RuntimeOrigin::signed(creator), kitty_id, SALE_PRICE, None));
+		assert_ok!(KittiesModule::buy(RuntimeOrigin::signed(collector), kitty_id, SALE_PRICE));
+
+		// 二次出售时创作者收取版税
+		let pallet_balance = Balances::free_balance(KittiesModule::get_account_id());
+		let creator_balance = Balances::free_balance(creator);
+		let collector_balance = Balances::free_balance(collector);
+
+		assert_ok!(KittiesModule::sale(RuntimeOrigin::signed(collector), kitty_id, SALE_PRICE, None));
+		assert_ok!(KittiesModule::buy(RuntimeOrigin::signed(buyer), kitty_id, SALE_PRICE));
+
+		assert_eq!(Balances::free_balance(KittiesModule::get_account_id()), pallet_balance + fee);
+		assert_eq!(Balances::free_balance(creator), creator_balance + royalty);
+		assert_eq!(
+			Balances::free_balance(collector),
+			collector_balance + SALE_PRICE - fee - royalty
+		);
+		System::assert_has_event(
+			Event::KittyBought { who: buyer, kitty_id, price: SALE_PRICE, fee, royalty }.into()
+		);
+	});
+}
+
+#[test]
+fn it_withholds_unpayable_fee_and_royalty() {
+	use frame_support::traits::tokens::nonfungible::Mutate;
+
+	new_test_ext().execute_with(|| {
+		let kitty_id: u32 = 0;
+		let creator: u64 = 1;
+		let collector: u64 = 2;
+		let offerer: u64 = 3;
+		let pallet_account = KittiesModule::get_account_id();
+
+		// 没有人支付过铸造费用, 模块账户不存在, 低于 ED 的手续费留给卖家
+		assert_ok!(<KittiesModule as Mutate<u64>>::mint_into(&kitty_id, &creator));
+		assert!(MarketplaceFee::get() * SALE_PRICE < EXISTENTIAL_DEPOSIT);
+		assert_ok!(KittiesModule::sale(RuntimeOrigin::signed(creator), kitty_id, SALE_PRICE, None));
+		let creator_balance = Balances::free_balance(creator);
+		assert_ok!(KittiesModule::buy(RuntimeOrigin::signed(collector), kitty_id, SALE_PRICE));
+		assert_eq!(Balances::total_balance(&pallet_account), 0);
+		assert_eq!(Balances::free_balance(creator), creator_balance + SALE_PRICE);
+		System::assert_has_event(
+			Event::KittyBought { who: collector, kitty_id, price: SALE_PRICE, fee: 0, royalty: 0 }.into()
+		);
+
+		// 创作者账户已被回收, 从保留余额支付时手续费和版税都留给卖家
+		assert_ok!(Balances::transfer_all(RuntimeOrigin::signed(creator), 4, false));
+		assert_eq!(Balances::total_balance(&creator), 0);
+		assert_ok!(KittiesModule::make_offer(RuntimeOrigin::signed(offerer), kitty_id, SALE_PRICE, 10));
+		let collector_balance = Balances::free_balance(collector);
+		assert_ok!(KittiesModule::accept_offer(RuntimeOrigin::signed(collector), kitty_id, offerer));
+		assert_eq!(KittiesModule::kitty_owner(kitty_id), Some(offerer));
+		assert_eq!(Balances::reserved_balance(offerer), 0);
+		assert_eq!(Balances::free_balance(collector), collector_balance + SALE_PRICE);
+		assert_eq!(Balances::total_balance(&creator), 0);
+		assert_eq!(Balances::total_balance(&pallet_account), 0);
+	});
+}
+
+#[test]
+fn it_works_for_genesis_config() {
+	use frame_support::traits::GenesisBuild;
+
+	let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	crate::GenesisConfig::<Test> {
+		kitties: vec![
+			(1, [1; 16], b"tom".to_vec()),
+			(1, [2; 16], vec![]),
+			(2, [3; 16], b"kiki".to_vec()),
+		],
+		next_kitty_id: 0,
+	}
+	.assimilate_storage(&mut t)
+	.unwrap();
+
+	sp_io::TestExternalities::from(t).execute_with(|| {
+		// 创世小猫按顺序分配编号，下一个编号不小于创世小猫数量
+		assert_eq!(KittiesModule::next_kitty_id(), 3);
+		assert_eq!(KittiesModule::kitties(0).map(|kitty| kitty.dna), Some([1; 16]));
+		assert_eq!(
+			KittiesModule::kitties(2).map(|kitty| kitty.name.into_inner()),
+			Some(b"kiki".to_vec())
+		);
+		assert_eq!(KittiesModule::kitty_owner(1), Some(1));
+		assert_eq!(KittiesModule::kitty_creator(2), Some(2));
+		assert_eq!(KittiesModule::kitties_of(&1), vec![0, 1]);
+		assert_eq!(KittiesModule::name_deposit(0), None);
+
+		// 创世小猫没有支付铸造费用, 销毁时不退款
+		assert_eq!(KittiesModule::mint_price(0), Some(0));
+		System::set_block_number(1);
+		assert_ok!(KittiesModule::burn(RuntimeOrigin::signed(1), 0));
+		System::assert_last_event(Event::KittyBurned { who: 1, kitty_id: 0, refund: 0 }.into());
+	});
+}
+
+#[test]
+fn it_works_for_traits_and_rarity() {
+	new_test_ext().execute_with(|| {
+		let account_id: u64 = 1;
+
+		// 特征由 DNA 的字节按变体数量取模得到
+		let traits = crate::KittyTraits::from_dna(&[17, 9, 0, 255, 6, 13, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+		assert_eq!(traits.variants(), [1, 1, 0, 15, 2, 5]);
+
+		assert_ok!(KittiesModule::create(RuntimeOrigin::signed(account_id), KITTY_NAME.to_vec()));
+		assert_ok!(KittiesModule::create(RuntimeOrigin::signed(account_id), KITTY_NAME.to_vec()));
+		assert_eq!(KittiesModule::kitty_population(), 2);
+
+		let dna = KittiesModule::kitties(0).unwrap().dna;
+		let traits = KittiesModule::traits_of(0).unwrap();
+		assert_eq!(traits, crate::KittyTraits::from_dna(&dna));
+		assert!(KittiesModule::trait_count(0, traits.fur_color) >= 1);
+
+		// 每个特征的稀有度为总数除以同变体数量
+		let expected: u64 = traits
+			.variants()
+			.into_iter()
+			.enumerate()
+			.map(|(index, variant)| 200 / KittiesModule::trait_count(index as u8, variant) as u64)
+			.sum();
+		assert_eq!(KittiesModule::rarity_score(0), Some(expected));
+		assert_eq!(KittiesModule::rarity_score(2), None);
+
+		// 销毁后统计随之减少
+		assert_ok!(KittiesModule::burn(RuntimeOrigin::signed(account_id), 1));
+		assert_ok!(KittiesModule::burn(RuntimeOrigin::signed(account_id), 0));
+		assert_eq!(KittiesModule::kitty_population(), 0);
+		assert_eq!(KittiesModule::trait_count(0, traits.fur_color), 0);
+	});
+}
+
+#[test]
+fn it_works_for_commit_reveal_mint() {
+	new_test_ext().execute_with(|| {
+		use codec::Encode;
+		use sp_runtime::traits::{BlakeTwo256, Hash};
+
+		let account_id: u64 = 1;
+		let secret = [7u8; 32];
+		let commitment = BlakeTwo256::hash(&secret);
+
+		assert_ok!(KittiesModule::create(RuntimeOrigin::signed(account_id), KITTY_NAME.to_vec()));
+		assert_ok!(KittiesModule::create(RuntimeOrigin::signed(account_id), KITTY_NAME.to_vec()));
+		let reserved = Balances::reserved_balance(account_id);
+
+		assert_noop!(
+			KittiesModule::commit_mint(RuntimeOrigin::signed(account_id), commitment, Some((0, 0)), vec![]),
+			Error::<Test>::SameKittyId
+		);
+		assert_noop!(
+			KittiesModule::commit_mint(RuntimeOrigin::signed(2), commitment, Some((0, 1)), vec![]),
+			Error::<Test>::NotOwner
+		);
+
+		// 提交承诺时预留小猫价格
+		assert_ok!(KittiesModule::commit_mint(
+			RuntimeOrigin::signed(account_id),
+			commitment,
+			None,
+			KITTY_NAME.to_vec()
+		));
+		assert_eq!(Balances::reserved_balance(account_id), reserved + KittyPrice::get());
+		System::assert_last_event(
+			Event::MintCommitted { who: account_id, commitment, reveal_at: 3, expiry: 11 }.into()
+		);
+		assert_noop!(
+			KittiesModule::commit_mint(RuntimeOrigin::signed(account_id), commitment, None, vec![]),
+			Error::<Test>::DuplicateCommitment
+		);
+
+		// 等待期内不能揭示，其他账户无法揭示
+		assert_noop!(
+			KittiesModule::reveal_mint(RuntimeOrigin::signed(account_id), secret),
+			Error::<Test>::RevealTooEarly
+		);
+		run_to_block(3);
+		assert_noop!(
+			KittiesModule::reveal_mint(RuntimeOrigin::signed(2), secret),
+			Error::<Test>::NoCommitment
+		);
+
+		// 揭示后 DNA 由秘密和承诺区块的哈希得出
+		assert_ok!(KittiesModule::reveal_mint(RuntimeOrigin::signed(account_id), secret));
+		let dna = (secret, System::block_hash(1)).using_encoded(sp_io::hashing::blake2_128);
+		assert_eq!(KittiesModule::kitties(2).map(|kitty| kitty.dna), Some(dna));
+		assert_eq!(KittiesModule::kitty_owner(2), Some(account_id));
+		assert_eq!(KittiesModule::commitment(account_id, commitment), None);
+		assert!(KittiesModule::commitments_expiring(11).is_empty());
+		assert_eq!(
+			Balances::reserved_balance(account_id),
+			reserved + NameDepositPerByte::get() * KITTY_NAME.len() as u128
+		);
+
+		// 繁殖也可以先提交后揭示
+		let secret = [8u8; 32];
+		let commitment = BlakeTwo256::hash(&secret);
+		assert_ok!(KittiesModule::commit_mint(
+			RuntimeOrigin::signed(account_id),
+			commitment,
+			Some((0, 1)),
+			KITTY_NAME.to_vec()
+		));
+		run_to_block(5);
+		assert_ok!(KittiesModule::reveal_mint(RuntimeOrigin::signed(account_id), secret));
+		assert_eq!(KittiesModule::kitty_parents(3), Some((0, 1)));
+		assert_eq!(KittiesModule::kitty_owner(3), Some(account_id));
+	});
+}
+
+#[test]
+fn it_passes_integrity_test() {
+	use frame_support::traits::Hooks;
+
+	// 揭示延迟和承诺超时的配置要一致
+	KittiesModule::integrity_test();
+}
+
+#[test]
+fn it_fails_to_mint_without_randomness() {
+	new_test_ext().execute_with(|| {
+		use frame_support::traits::tokens::nonfungible::Mutate;
+		use sp_runtime::traits::{BlakeTwo256, Hash};
+
+		let account_id: u64 = 1;
+		assert_ok!(KittiesModule::create(RuntimeOrigin::signed(account_id), KITTY_NAME.to_vec()));
+		assert_ok!(KittiesModule::create(RuntimeOrigin::signed(account_id), KITTY_NAME.to_vec()));
+
+		// 随机源没有输出时一步铸造和繁殖都失败
+		RandomnessAvailable::set(false);
+		assert_noop!(
+			KittiesModule::create(RuntimeOrigin::signed(account_id), KITTY_NAME.to_vec()),
+			Error::<Test>::RandomnessUnavailable
+		);
+		assert_noop!(
+			KittiesModule::breed(RuntimeOrigin::signed(account_id), 0, 1, KITTY_NAME.to_vec()),
+			Error::<Test>::RandomnessUnavailable
+		);
+		assert_noop!(
+			<KittiesModule as Mutate<u64>>::mint_into(&2, &account_id),
+			Error::<Test>::RandomnessUnavailable
+		);
+
+		// 先提交后揭示不依赖随机源
+		let secret = [7u8; 32];
+		assert_ok!(KittiesModule::commit_mint(
+			RuntimeOrigin::signed(account_id),
+			BlakeTwo256::hash(&secret),
+			None,
+			KITTY_NAME.to_vec()
+		));
+		run_to_block(3);
+		assert_ok!(KittiesModule::reveal_mint(RuntimeOrigin::signed(account_id), secret));
+		assert_eq!(KittiesModule::kitty_owner(2), Some(account_id));
+	});
+}
+
+#[test]
+fn it_works_for_expired_commitment() {
+	new_test_ext().execute_with(|| {
+		use sp_runtime::traits::{BlakeTwo256, Hash};
+
+		let account_id: u64 = 1;
+		let secret = [7u8; 32];
+		let commitment = BlakeTwo256::hash(&secret);
+		let reserved = Balances::reserved_balance(account_id);
+		let free = Balances::free_balance(account_id);
+		let issuance = Balances::total_issuance();
+
+		assert_ok!(KittiesModule::commit_mint(
+			RuntimeOrigin::signed(account_id),
+			commitment,
+			None,
+			KITTY_NAME.to_vec()
+		));
+
+		// 超时未揭示的承诺被移除, 罚没部分押金后退还其余部分
+		run_to_block(11);
+		let forfeited = CommitmentForfeit::get() * KittyPrice::get();
+		assert_eq!(KittiesModule::commitment(account_id, commitment), None);
+		assert_eq!(Balances::reserved_balance(account_id), reserved);
+		assert_eq!(Balances::free_balance(account_id), free - forfeited);
+		assert_eq!(Balances::total_issuance(), issuance - forfeited);
+		System::assert_has_event(
+			Event::CommitmentExpired {
+				who: account_id,
+				commitment,
+				deposit: KittyPrice::get(),
+				forfeited,
+			}
+			.into()
+		);
+		assert_noop!(
+			KittiesModule::reveal_mint(RuntimeOrigin::signed(account_id), secret),
+			Error::<Test>::NoCommitment
+		);
+	});
+}
+
+#[test]
+fn it_works_for_battle() {
+	new_test_ext().execute_with(|| {
+		let challenger: u64 = 1;
+		let defender: u64 = 2;
+		let stake: Balance = 1_000;
+
+		assert_ok!(KittiesModule::create(RuntimeOrigin::signed(challenger), KITTY_NAME.to_vec()));
+		assert_ok!(KittiesModule::create(RuntimeOrigin::signed(defender), KITTY_NAME.to_vec()));
+		assert_ok!(KittiesModule::create(RuntimeOrigin::signed(defender), KITTY_NAME.to_vec()));
+
+		assert_noop!(
+			KittiesModule::challenge(RuntimeOrigin::signed(challenger), 1, 2, stake),
+			Error::<Test>::NotOwner
+		);
+		assert_noop!(
+			KittiesModule::challenge(RuntimeOrigin::signed(defender), 1, 2, stake),
+			Error::<Test>::AlreadyOwned
+		);
+		assert_noop!(
+			KittiesModule::accept_challenge(RuntimeOrigin::signed(defender), 1),
+			Error::<Test>::NoChallenge
+		);
+
+		// 发起挑战时预留赌注
+		let challenger_reserved = Balances::reserved_balance(challenger);
+		assert_ok!(KittiesModule::challenge(RuntimeOrigin::signed(challenger), 0, 1, stake));
+		assert_eq!(Balances::reserved_balance(challenger), challenger_reserved + stake);
+		System::assert_last_event(
+			Event::ChallengeIssued { who: challenger, kitty_id: 0, opponent: 1, stake }.into()
+		);
+		assert_noop!(
+			KittiesModule::challenge(RuntimeOrigin::signed(defender), 2, 0, stake),
+			Error::<Test>::KittyInBattle
+		);
+
+		// 挑战中的小猫不能转移或出售
+		assert_noop!(
+			KittiesModule::transfer(RuntimeOrigin::signed(challenger), 3, 0),
+			Error::<Test>::KittyInBattle
+		);
+		assert_noop!(
+			KittiesModule::sale(RuntimeOrigin::signed(defender), 1, SALE_PRICE, None),
+			Error::<Test>::KittyInBattle
+		);
+
+		assert_noop!(
+			KittiesModule::accept_challenge(RuntimeOrigin::signed(challenger), 1),
+			Error::<Test>::NotOwner
+		);
+
+		let challenger_free = Balances::free_balance(challenger);
+		let defender_free = Balances::free_balance(defender);
+		let defender_reserved = Balances::reserved_balance(defender);
+
+		// 接受挑战时预留同样的赌注，战斗在下一个区块进行
+		assert_ok!(KittiesModule::accept_challenge(RuntimeOrigin::signed(defender), 1));
+		assert_eq!(Balances::reserved_balance(defender), defender_reserved + stake);
+		assert_eq!(KittiesModule::battles_due(2).into_inner(), vec![1]);
+		System::assert_last_event(
+			Event::ChallengeAccepted { who: defender, kitty_id: 1, opponent: 0, battle_at: 2 }.into()
+		);
+
+		// 接受后不能再接受或取消
+		assert_noop!(
+			KittiesModule::accept_challenge(RuntimeOrigin::signed(defender), 1),
+			Error::<Test>::ChallengeAlreadyAccepted
+		);
+		assert_noop!(
+			KittiesModule::cancel_challenge(RuntimeOrigin::signed(challenger), 1),
+			Error::<Test>::ChallengeAlreadyAccepted
+		);
+		assert!(KittiesModule::in_battle(0));
+		assert!(KittiesModule::in_battle(1));
+
+		run_to_block(2);
+
+		// 胜者获得对方赌注和经验
+		let (winner, loser) = match System::events().last().map(|record| record.event.clone()) {
+			Some(RuntimeEvent::KittiesModule(Event::BattleFought { winner, loser, .. })) =>
+				(winner, loser),
+			event => panic!("unexpected event {:?}", event),
+		};
+		if winner == 0 {
+			assert_eq!(Balances::free_balance(challenger), challenger_free + stake * 2);
+			assert_eq!(Balances::free_balance(defender), defender_free - stake);
+		} else {
+			assert_eq!(Balances::free_balance(challenger), challenger_free);
+			assert_eq!(Balances::free_balance(defender), defender_free + stake);
+		}
+		assert_eq!(Balances::reserved_balance(challenger), challenger_reserved);
+		assert_eq!(Balances::reserved_balance(defender), defender_reserved);
+		assert_eq!(
+			KittiesModule::kitty_stats(winner),
+			crate::KittyStats { wins: 1, losses: 0, experience: 10, level: 0 }
+		);
+		assert_eq!(KittiesModule::kitty_stats(loser).losses, 1);
+		System::assert_last_event(
+			Event::BattleFought { winner, loser, paid: stake, winner_level: 0 }.into()
+		);
+		assert_eq!(KittiesModule::accepted_challenge(1), None);
+
+		// 战斗结束后解锁
+		assert!(!KittiesModule::in_battle(0));
+		assert!(!KittiesModule::in_battle(1));
+		assert_ok!(KittiesModule::transfer(RuntimeOrigin::signed(challenger), 3, 0));
+	});
+}
+
+#[test]
+fn it_pays_what_is_left_of_a_slashed_stake() {
+	use frame_support::traits::ReservableCurrency;
+
+	new_test_ext().execute_with(|| {
+		let challenger: u64 = 1;
+		let defender: u64 = 2;
+		let stake: Balance = 1_000;
+		let left: Balance = 600;
+
+		assert_ok!(KittiesModule::create(RuntimeOrigin::signed(challenger), KITTY_NAME.to_vec()));
+		assert_ok!(KittiesModule::create(RuntimeOrigin::signed(defender), KITTY_NAME.to_vec()));
+		assert_ok!(KittiesModule::challenge(RuntimeOrigin::signed(challenger), 0, 1, stake));
+		assert_ok!(KittiesModule::accept_challenge(RuntimeOrigin::signed(defender), 1));
+
+		// 双方的预留都被罚没到只剩一部分赌注
+		for who in [challenger, defender] {
+			let _ = Balances::slash_reserved(&who, Balances::reserved_balance(who) - left);
+		}
+		let challenger_free = Balances::free_balance(challenger);
+		let defender_free = Balances::free_balance(defender);
+
+		run_to_block(2);
+
+		// 胜者只拿到败者剩下的部分, 事件里记录实际支付的金额
+		let (winner, loser) = match System::events().last().map(|record| record.event.clone()) {
+			Some(RuntimeEvent::KittiesModule(Event::BattleFought { winner, loser, .. })) =>
+				(winner, loser),
+			event => panic!("unexpected event {:?}", event),
+		};
+		System::assert_last_event(
+			Event::BattleFought { winner, loser, paid: left, winner_level: 0 }.into()
+		);
+		let (winner_account, winner_free) =
+			if winner == 0 { (challenger, challenger_free) } else { (defender, defender_free) };
+		assert_eq!(Balances::free_balance(winner_account), winner_free + left * 2);
+		assert_eq!(Balances::reserved_balance(challenger), 0);
+		assert_eq!(Balances::reserved_balance(defender), 0);
+	});
+}
+
+#[test]
+fn it_works_for_cancel_challenge() {
+	new_test_ext().execute_with(|| {
+		let challenger: u64 = 1;
+		let defender: u64 = 2;
+		let stake: Balance = 1_000;
+
+		assert_ok!(KittiesModule::create(RuntimeOrigin::signed(challenger), KITTY_NAME.to_vec()));
+		assert_ok!(KittiesModule::create(RuntimeOrigin::signed(defender), KITTY_NAME.to_vec()));
+		let reserved = Balances::reserved_balance(challenger);
+
+		// 挑战者可以撤回，被挑战者可以拒绝
+		assert_ok!(KittiesModule::challenge(RuntimeOrigin::signed(challenger), 0, 1, stake));
+		// 挑战期间不能出租
+		assert_noop!(
+			KittiesModule::offer_rental(RuntimeOrigin::signed(defender), 1, 10, 5),
+			Error::<Test>::KittyInBattle
+		);
+		assert_noop!(
+			KittiesModule::cancel_challenge(RuntimeOrigin::signed(3), 1),
+			Error::<Test>::NotOwner
+		);
+		assert_ok!(KittiesModule::cancel_challenge(RuntimeOrigin::signed(challenger), 1));
+		assert_eq!(Balances::reserved_balance(challenger), reserved);
+		System::assert_last_event(Event::ChallengeCancelled { kitty_id: 0, opponent: 1 }.into());
+
+		assert_ok!(KittiesModule::offer_rental(RuntimeOrigin::signed(challenger), 0, 10, 5));
+		assert_ok!(KittiesModule::challenge(RuntimeOrigin::signed(challenger), 0, 1, stake));
+		assert_noop!(
+			KittiesModule::rent(RuntimeOrigin::signed(3), 0, 5),
+			Error::<Test>::KittyInBattle
+		);
+		assert_ok!(KittiesModule::cancel_challenge(RuntimeOrigin::signed(defender), 1));
+		assert_eq!(Balances::reserved_balance(challenger), reserved);
+		assert!(!KittiesModule::in_battle(0));
+		assert_noop!(
+			KittiesModule::cancel_challenge(RuntimeOrigin::signed(challenger), 1),
+			Error::<Test>::NoChallenge
+		);
+	});
+}
+
+#[test]
+fn it_works_for_batch_transfer() {
+	new_test_ext().execute_with(|| {
+		let account_id: u64 = 1;
+
+		for _ in 0..3 {
+			assert_ok!(KittiesModule::create(RuntimeOrigin::signed(account_id), KITTY_NAME.to_vec()));
+		}
+
+		assert_noop!(
+			KittiesModule::batch_transfer(RuntimeOrigin::signed(account_id), vec![(2, 0); 6]),
+			Error::<Test>::BatchTooLarge
+		);
+
+		// 任一转移失败则整批回滚
+		assert_noop!(
+			KittiesModule::batch_transfer(RuntimeOrigin::signed(account_id), vec![(2, 0), (3, 1), (2, 5)]),
+			Error::<Test>::InvalidKittyId
+		);
+		assert_eq!(KittiesModule::kitties_of(&account_id), vec![0, 1, 2]);
+
+		assert_ok!(KittiesModule::batch_transfer(RuntimeOrigin::signed(account_id), vec![(2, 0), (3, 1)]));
+		assert_eq!(KittiesModule::kitty_owner(0), Some(2));
+		assert_eq!(KittiesModule::kitty_owner(1), Some(3));
+		assert_eq!(KittiesModule::kitties_of(&account_id), vec![2]);
+		System::assert_last_event(Event::KittyTransferred { from: account_id, to: 3, kitty_id: 1 }.into());
+	});
+}
+
+#[test]
+fn it_works_for_batch_sale() {
+	new_test_ext().execute_with(|| {
+		let account_id: u64 = 1;
+
+		for _ in 0..2 {
+			assert_ok!(KittiesModule::create(RuntimeOrigin::signed(account_id), KITTY_NAME.to_vec()));
+		}
+		assert_ok!(KittiesModule::create(RuntimeOrigin::signed(2), KITTY_NAME.to_vec()));
+
+		// 任一上架失败则整批回滚
+		assert_noop!(
+			KittiesModule::batch_sale(RuntimeOrigin::signed(account_id), vec![(0, SALE_PRICE), (2, SALE_PRICE)]),
+			Error::<Test>::NotOwner
+		);
+		assert_noop!(
+			KittiesModule::batch_sale(RuntimeOrigin::signed(account_id), vec![(0, SALE_PRICE); 6]),
+			Error::<Test>::BatchTooLarge
+		);
+
+		assert_ok!(KittiesModule::batch_sale(
+			RuntimeOrigin::signed(account_id),
+			vec![(0, SALE_PRICE), (1, SALE_PRICE + 1)]
+		));
+		assert_eq!(KittiesModule::on_sale().len(), 2);
+		assert_eq!(KittiesModule::kitty_on_sale(1), Some(SALE_PRICE + 1));
+		assert_eq!(KittiesModule::sale_expiry(0), None);
+	});
+}
+
+#[test]
+fn it_works_for_kitty_hooks() {
+	new_test_ext().execute_with(|| {
+		let account_id: u64 = 1;
+
+		// 创建和繁殖都会通知 OnKittyCreated
+		assert_ok!(KittiesModule::create(RuntimeOrigin::signed(account_id), KITTY_NAME.to_vec()));
+		assert_ok!(KittiesModule::create(RuntimeOrigin::signed(account_id), KITTY_NAME.to_vec()));
+		assert_ok!(KittiesModule::breed(RuntimeOrigin::signed(account_id), 0, 1, KITTY_NAME.to_vec()));
+		assert_eq!(
+			CreatedKitties::get(),
+			vec![(account_id, 0, None), (account_id, 1, None), (account_id, 2, Some((0, 1)))]
+		);
+
+		// 转移、购买和销毁都会通知 OnKittyTransfer
+		assert_ok!(KittiesModule::transfer(RuntimeOrigin::signed(account_id), 2, 0));
+		assert_ok!(KittiesModule::sale(RuntimeOrigin::signed(account_id), 1, SALE_PRICE, None));
+		assert_ok!(KittiesModule::buy(RuntimeOrigin::signed(3), 1, SALE_PRICE));
+		assert_ok!(KittiesModule::burn(RuntimeOrigin::signed(account_id), 2));
+		assert_eq!(KittyTransfers::get(), vec![(account_id, Some(2), 0), (account_id, Some(3), 1), (account_id, None, 2)]);
+
+		// 失败的调用不会通知
+		assert_noop!(KittiesModule::transfer(RuntimeOrigin::signed(account_id), 2, 0), Error::<Test>::NotOwner);
+		assert_eq!(KittyTransfers::get().len(), 3);
+	});
+}
+
+#[test]
+fn it_works_for_provenance() {
+	new_test_ext().execute_with(|| {
+		use crate::{Acquisition, ProvenanceRecord};
+
+		let kitty_id: u32 = 0;
+		let record = |owner: u64, block: u64, acquisition| ProvenanceRecord { owner, block, acquisition };
+
+		assert_ok!(KittiesModule::create(RuntimeOrigin::signed(1), KITTY_NAME.to_vec()));
+		run_to_block(3);
+		assert_ok!(KittiesModule::transfer(RuntimeOrigin::signed(1), 2, kitty_id));
+		run_to_block(4);
+		assert_ok!(KittiesModule::sale(RuntimeOrigin::signed(2), kitty_id, SALE_PRICE, None));
+		assert_ok!(KittiesModule::buy(RuntimeOrigin::signed(3), kitty_id, SALE_PRICE));
+
+		assert_eq!(
+			KittiesModule::provenance(kitty_id).into_inner(),
+			vec![
+				record(1, 1, Acquisition::Created),
+				record(2, 3, Acquisition::Transferred),
+				record(3, 4, Acquisition::Bought),
+			]
+		);
+
+		// 超过 MaxProvenanceLength 时丢弃最早的记录
+		assert_ok!(KittiesModule::transfer(RuntimeOrigin::signed(3), 1, kitty_id));
+		assert_ok!(KittiesModule::transfer(RuntimeOrigin::signed(1), 2, kitty_id));
+		let provenance = KittiesModule::provenance(kitty_id);
+		assert_eq!(provenance.len(), 4);
+		assert_eq!(provenance[0], record(2, 3, Acquisition::Transferred));
+		assert_eq!(provenance[3], record(2, 4, Acquisition::Transferred));
+
+		// 销毁后记录被清除
+		assert_ok!(KittiesModule::burn(RuntimeOrigin::signed(2), kitty_id));
+		assert!(KittiesModule::provenance(kitty_id).is_empty());
+	});
+}
+
+#[test]
+fn it_works_for_lineage() {
+	new_test_ext().execute_with(|| {
+		let account_id: u64 = 1;
+
+		// 0 + 1 -> 2, 2 + 3 -> 4, 之后 0 + 2 -> 5
+		assert_ok!(KittiesModule::create(RuntimeOrigin::signed(account_id), KITTY_NAME.to_vec()));
+		assert_ok!(KittiesModule::create(RuntimeOrigin::signed(account_id), KITTY_NAME.to_vec()));
+		assert_ok!(KittiesModule::breed(RuntimeOrigin::signed(account_id), 0, 1, KITTY_NAME.to_vec()));
+		assert_ok!(KittiesModule::create(RuntimeOrigin::signed(account_id), KITTY_NAME.to_vec()));
+		assert_ok!(KittiesModule::breed(RuntimeOrigin::signed(account_id), 2, 3, KITTY_NAME.to_vec()));
+
+		assert_eq!(KittiesModule::ancestors(4, 1), vec![2, 3]);
+		assert_eq!(KittiesModule::ancestors(4, 10), vec![2, 3, 0, 1]);
+		assert_eq!(KittiesModule::ancestors(0, 10), Vec::<u32>::new());
+
+		run_to_block(20);
+		assert_ok!(KittiesModule::breed(RuntimeOrigin::signed(account_id), 0, 2, KITTY_NAME.to_vec()));
+
+		// 近亲繁殖时祖先只列出一次
+		assert_eq!(KittiesModule::ancestors(5, 2), vec![0, 2, 1]);
+
+		let mut descendants = KittiesModule::descendants(0);
+		descendants.sort();
+		assert_eq!(descendants, vec![2, 4, 5]);
+		assert_eq!(KittiesModule::descendants(3), vec![4]);
+
+		// 有后代的小猫被销毁后仍留在家谱中
+		assert_ok!(KittiesModule::burn(RuntimeOrigin::signed(account_id), 2));
+		assert_eq!(KittiesModule::kitty_parents(2), Some((0, 1)));
+		let mut descendants = KittiesModule::descendants(1);
+		descendants.sort();
+		assert_eq!(descendants, vec![2, 4, 5]);
+		assert_eq!(KittiesModule::ancestors(4, 10), vec![2, 3, 0, 1]);
+
+		// 最后一个后代被销毁时清理其家谱记录
+		assert_ok!(KittiesModule::burn(RuntimeOrigin::signed(account_id), 4));
+		assert_eq!(KittiesModule::kitty_parents(2), Some((0, 1)));
+		assert_ok!(KittiesModule::burn(RuntimeOrigin::signed(account_id), 5));
+		assert_eq!(KittiesModule::kitty_parents(2), None);
+		assert_eq!(crate::KittyChildren::<Test>::iter_prefix(2).count(), 0);
+		assert_eq!(crate::KittyChildren::<Test>::iter().count(), 0);
+		assert_eq!(KittiesModule::descendants(0), Vec::<u32>::new());
+		assert_eq!(KittiesModule::descendants(1), Vec::<u32>::new());
 	});
 }
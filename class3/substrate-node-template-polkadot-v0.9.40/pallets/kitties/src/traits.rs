@@ -0,0 +1,19 @@
+//! Hooks other pallets implement to react to kitties changing hands.
+
+use crate::KittyId;
+
+/// Called after a kitty is created, bred or minted through `nonfungible::Mutate`.
+#[impl_trait_for_tuples::impl_for_tuples(30)]
+pub trait OnKittyCreated<AccountId> {
+	/// `parents` is set for bred kitties.
+	fn on_kitty_created(owner: &AccountId, kitty_id: KittyId, parents: Option<(KittyId, KittyId)>);
+}
+
+/// Called after a kitty changes owner, whether transferred, bought, won in an auction or sold
+/// to an offer, and after it is burned.
+#[impl_trait_for_tuples::impl_for_tuples(30)]
+pub trait OnKittyTransfer<AccountId> {
+	fn on_kitty_transfer(from: &AccountId, to: &AccountId, kitty_id: KittyId);
+
+	fn on_kitty_burned(owner: &AccountId, kitty_id: KittyId);
+}
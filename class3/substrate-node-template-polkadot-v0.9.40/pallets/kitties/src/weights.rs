@@ -0,0 +1,665 @@
+//! Weights for pallet_kitties
+//!
+//! These are estimates kept in the layout of the benchmark CLI output, not measured values. The
+//! storage comments list what each call touches in its worst case. Replace this file with the
+//! output of the benchmarks after changing any of the extrinsics:
+
+// ./target/release/node-template
+// benchmark
+// pallet
+// --chain=dev
+// --pallet=pallet_kitties
+// --extrinsic=*
+// --steps=50
+// --repeat=20
+// --execution=wasm
+// --wasm-execution=compiled
+// --output=pallets/kitties/src/weights.rs
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for pallet_kitties.
+pub trait WeightInfo {
+	fn create() -> Weight;
+	fn breed() -> Weight;
+	fn transfer() -> Weight;
+	fn sale() -> Weight;
+	fn buy() -> Weight;
+	fn update_price() -> Weight;
+	fn create_auction() -> Weight;
+	fn bid() -> Weight;
+	fn settle_auction() -> Weight;
+	fn fight_battle() -> Weight;
+	fn make_offer() -> Weight;
+	fn withdraw_offer() -> Weight;
+	fn accept_offer() -> Weight;
+	fn cancel_sale() -> Weight;
+	fn burn() -> Weight;
+	fn rename() -> Weight;
+	fn approve() -> Weight;
+	fn set_approval_for_all() -> Weight;
+	fn transfer_from() -> Weight;
+	fn offer_rental() -> Weight;
+	fn cancel_rental_offer() -> Weight;
+	fn rent() -> Weight;
+	fn offer_siring() -> Weight;
+	fn cancel_siring_offer() -> Weight;
+	fn breed_with_sire() -> Weight;
+	fn commit_mint() -> Weight;
+	fn reveal_mint() -> Weight;
+	fn challenge() -> Weight;
+	fn accept_challenge() -> Weight;
+	fn cancel_challenge() -> Weight;
+	fn batch_transfer(n: u32, ) -> Weight;
+	fn batch_sale(n: u32, ) -> Weight;
+}
+
+/// Weights for pallet_kitties using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	/// Storage: Babe Randomness (r:1 w:0)
+	/// Storage: Babe EpochStart (r:1 w:0)
+	/// Storage: System Account (r:2 w:2)
+	/// Storage: KittiesModule NextKittyId (r:1 w:1)
+	/// Storage: KittiesModule Kitties (r:0 w:1)
+	/// Storage: KittiesModule KittyOwner (r:0 w:1)
+	/// Storage: KittiesModule KittyCreator (r:0 w:1)
+	/// Storage: KittiesModule MintPrices (r:0 w:1)
+	/// Storage: KittiesModule OwnedKitties (r:1 w:1)
+	/// Storage: KittiesModule KittyPopulation (r:1 w:1)
+	/// Storage: KittiesModule TraitCounts (r:6 w:6)
+	/// Storage: KittiesModule Provenance (r:1 w:1)
+	/// Storage: KittiesModule NameDeposits (r:0 w:1)
+	fn create() -> Weight {
+		Weight::from_parts(66_000_000, 7_000)
+			.saturating_add(T::DbWeight::get().reads(14_u64))
+			.saturating_add(T::DbWeight::get().writes(17_u64))
+	}
+	/// Storage: KittiesModule Rentals (r:2 w:0)
+	/// Storage: KittiesModule KittyOwner (r:2 w:1)
+	/// Storage: KittiesModule Kitties (r:2 w:1)
+	/// Storage: Babe Randomness (r:1 w:0)
+	/// Storage: Babe EpochStart (r:1 w:0)
+	/// Storage: System Account (r:2 w:2)
+	/// Storage: KittiesModule NextKittyId (r:1 w:1)
+	/// Storage: KittiesModule KittyCreator (r:0 w:1)
+	/// Storage: KittiesModule MintPrices (r:0 w:1)
+	/// Storage: KittiesModule OwnedKitties (r:1 w:1)
+	/// Storage: KittiesModule KittyPopulation (r:1 w:1)
+	/// Storage: KittiesModule TraitCounts (r:6 w:6)
+	/// Storage: KittiesModule Provenance (r:1 w:1)
+	/// Storage: KittiesModule NameDeposits (r:0 w:1)
+	/// Storage: KittiesModule KittyParents (r:0 w:1)
+	/// Storage: KittiesModule KittyChildren (r:0 w:2)
+	/// Storage: KittiesModule KittyGeneration (r:2 w:1)
+	/// Storage: KittiesModule LastBred (r:2 w:2)
+	fn breed() -> Weight {
+		Weight::from_parts(85_000_000, 18_000)
+			.saturating_add(T::DbWeight::get().reads(24_u64))
+			.saturating_add(T::DbWeight::get().writes(23_u64))
+	}
+	/// Storage: KittiesModule KittyOwner (r:1 w:1)
+	/// Storage: KittiesModule Auctions (r:1 w:0)
+	/// Storage: KittiesModule Rentals (r:1 w:0)
+	/// Storage: KittiesModule Challenges (r:1 w:0)
+	/// Storage: KittiesModule ChallengingKitties (r:1 w:0)
+	/// Storage: KittiesModule OwnedKitties (r:2 w:2)
+	/// Storage: KittiesModule Provenance (r:1 w:1)
+	/// Storage: KittiesModule KittyApprovals (r:0 w:1)
+	/// Storage: KittiesModule RentalOffers (r:0 w:1)
+	/// Storage: KittiesModule SiringOffers (r:0 w:1)
+	/// Storage: KittiesModule SaleExpiry (r:1 w:1)
+	/// Storage: KittiesModule SalesExpiring (r:1 w:1)
+	/// Storage: KittiesModule KittyOnSale (r:1 w:1)
+	fn transfer() -> Weight {
+		Weight::from_parts(36_000_000, 8_000)
+			.saturating_add(T::DbWeight::get().reads(11_u64))
+			.saturating_add(T::DbWeight::get().writes(10_u64))
+	}
+	/// Storage: KittiesModule Kitties (r:1 w:0)
+	/// Storage: KittiesModule KittyOwner (r:1 w:0)
+	/// Storage: KittiesModule KittyOnSale (r:1 w:1)
+	/// Storage: KittiesModule Auctions (r:1 w:0)
+	/// Storage: KittiesModule Rentals (r:1 w:0)
+	/// Storage: KittiesModule Challenges (r:1 w:0)
+	/// Storage: KittiesModule ChallengingKitties (r:1 w:0)
+	/// Storage: KittiesModule SalesExpiring (r:1 w:1)
+	/// Storage: KittiesModule SaleExpiry (r:0 w:1)
+	fn sale() -> Weight {
+		Weight::from_parts(26_000_000, 6_000)
+			.saturating_add(T::DbWeight::get().reads(8_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	/// Storage: KittiesModule Kitties (r:1 w:0)
+	/// Storage: KittiesModule KittyOwner (r:1 w:1)
+	/// Storage: KittiesModule Rentals (r:1 w:0)
+	/// Storage: KittiesModule Challenges (r:1 w:0)
+	/// Storage: KittiesModule ChallengingKitties (r:1 w:0)
+	/// Storage: KittiesModule KittyCreator (r:1 w:0)
+	/// Storage: System Account (r:4 w:4)
+	/// Storage: KittiesModule OwnedKitties (r:2 w:2)
+	/// Storage: KittiesModule Provenance (r:1 w:1)
+	/// Storage: KittiesModule KittyApprovals (r:0 w:1)
+	/// Storage: KittiesModule RentalOffers (r:0 w:1)
+	/// Storage: KittiesModule SiringOffers (r:0 w:1)
+	/// Storage: KittiesModule SaleExpiry (r:1 w:1)
+	/// Storage: KittiesModule SalesExpiring (r:1 w:1)
+	/// Storage: KittiesModule KittyOnSale (r:1 w:1)
+	fn buy() -> Weight {
+		Weight::from_parts(82_000_000, 12_000)
+			.saturating_add(T::DbWeight::get().reads(16_u64))
+			.saturating_add(T::DbWeight::get().writes(14_u64))
+	}
+	/// Storage: KittiesModule Kitties (r:1 w:0)
+	/// Storage: KittiesModule KittyOwner (r:1 w:0)
+	/// Storage: KittiesModule KittyOnSale (r:1 w:1)
+	fn update_price() -> Weight {
+		Weight::from_parts(20_000_000, 4_000)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: KittiesModule Kitties (r:1 w:0)
+	/// Storage: KittiesModule KittyOwner (r:1 w:0)
+	/// Storage: KittiesModule KittyOnSale (r:1 w:0)
+	/// Storage: KittiesModule Rentals (r:1 w:0)
+	/// Storage: KittiesModule Challenges (r:1 w:0)
+	/// Storage: KittiesModule ChallengingKitties (r:1 w:0)
+	/// Storage: KittiesModule Auctions (r:1 w:1)
+	/// Storage: KittiesModule AuctionsEnding (r:1 w:1)
+	fn create_auction() -> Weight {
+		Weight::from_parts(28_000_000, 6_000)
+			.saturating_add(T::DbWeight::get().reads(8_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: KittiesModule Auctions (r:1 w:1)
+	/// Storage: System Account (r:2 w:2)
+	fn bid() -> Weight {
+		Weight::from_parts(40_000_000, 6_000)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	/// Storage: KittiesModule Auctions (r:1 w:1)
+	/// Storage: KittiesModule KittyCreator (r:1 w:0)
+	/// Storage: System Account (r:4 w:4)
+	/// Storage: KittiesModule KittyOwner (r:0 w:1)
+	/// Storage: KittiesModule OwnedKitties (r:2 w:2)
+	/// Storage: KittiesModule Provenance (r:1 w:1)
+	/// Storage: KittiesModule KittyApprovals (r:0 w:1)
+	/// Storage: KittiesModule RentalOffers (r:0 w:1)
+	/// Storage: KittiesModule SiringOffers (r:0 w:1)
+	fn settle_auction() -> Weight {
+		Weight::from_parts(76_000_000, 9_000)
+			.saturating_add(T::DbWeight::get().reads(9_u64))
+			.saturating_add(T::DbWeight::get().writes(12_u64))
+	}
+	/// Storage: KittiesModule AcceptedChallenges (r:1 w:1)
+	/// Storage: KittiesModule Challenges (r:1 w:1)
+	/// Storage: KittiesModule ChallengingKitties (r:0 w:1)
+	/// Storage: Babe Randomness (r:1 w:0)
+	/// Storage: Babe EpochStart (r:1 w:0)
+	/// Storage: System BlockHash (r:1 w:0)
+	/// Storage: KittiesModule Kitties (r:2 w:0)
+	/// Storage: KittiesModule Stats (r:2 w:2)
+	/// Storage: System Account (r:2 w:2)
+	fn fight_battle() -> Weight {
+		Weight::from_parts(58_000_000, 12_000)
+			.saturating_add(T::DbWeight::get().reads(11_u64))
+			.saturating_add(T::DbWeight::get().writes(7_u64))
+	}
+	/// Storage: KittiesModule KittyOwner (r:1 w:0)
+	/// Storage: KittiesModule Offers (r:1 w:1)
+	/// Storage: System Account (r:20 w:20)
+	fn make_offer() -> Weight {
+		Weight::from_parts(72_000_000, 12_000)
+			.saturating_add(T::DbWeight::get().reads(22_u64))
+			.saturating_add(T::DbWeight::get().writes(21_u64))
+	}
+	/// Storage: KittiesModule Offers (r:1 w:1)
+	/// Storage: System Account (r:20 w:20)
+	fn withdraw_offer() -> Weight {
+		Weight::from_parts(60_000_000, 11_000)
+			.saturating_add(T::DbWeight::get().reads(21_u64))
+			.saturating_add(T::DbWeight::get().writes(21_u64))
+	}
+	/// Storage: KittiesModule KittyOwner (r:1 w:1)
+	/// Storage: KittiesModule Auctions (r:1 w:0)
+	/// Storage: KittiesModule Rentals (r:1 w:0)
+	/// Storage: KittiesModule Challenges (r:1 w:0)
+	/// Storage: KittiesModule ChallengingKitties (r:1 w:0)
+	/// Storage: KittiesModule Offers (r:1 w:1)
+	/// Storage: KittiesModule KittyCreator (r:1 w:0)
+	/// Storage: System Account (r:23 w:23)
+	/// Storage: KittiesModule OwnedKitties (r:2 w:2)
+	/// Storage: KittiesModule Provenance (r:1 w:1)
+	/// Storage: KittiesModule KittyApprovals (r:0 w:1)
+	/// Storage: KittiesModule RentalOffers (r:0 w:1)
+	/// Storage: KittiesModule SiringOffers (r:0 w:1)
+	/// Storage: KittiesModule SaleExpiry (r:1 w:1)
+	/// Storage: KittiesModule SalesExpiring (r:1 w:1)
+	/// Storage: KittiesModule KittyOnSale (r:1 w:1)
+	fn accept_offer() -> Weight {
+		Weight::from_parts(116_000_000, 16_000)
+			.saturating_add(T::DbWeight::get().reads(36_u64))
+			.saturating_add(T::DbWeight::get().writes(34_u64))
+	}
+	/// Storage: KittiesModule KittyOwner (r:1 w:0)
+	/// Storage: KittiesModule SaleExpiry (r:1 w:1)
+	/// Storage: KittiesModule SalesExpiring (r:1 w:1)
+	/// Storage: KittiesModule KittyOnSale (r:1 w:1)
+	fn cancel_sale() -> Weight {
+		Weight::from_parts(22_000_000, 6_000)
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	/// Storage: KittiesModule KittyOwner (r:1 w:1)
+	/// Storage: KittiesModule Auctions (r:1 w:0)
+	/// Storage: KittiesModule Rentals (r:1 w:0)
+	/// Storage: KittiesModule Challenges (r:1 w:0)
+	/// Storage: KittiesModule ChallengingKitties (r:1 w:0)
+	/// Storage: KittiesModule MintPrices (r:1 w:1)
+	/// Storage: System Account (r:2 w:2)
+	/// Storage: KittiesModule SaleExpiry (r:1 w:1)
+	/// Storage: KittiesModule SalesExpiring (r:1 w:1)
+	/// Storage: KittiesModule KittyOnSale (r:1 w:1)
+	/// Storage: KittiesModule Offers (r:1 w:1)
+	/// Storage: KittiesModule OwnedKitties (r:1 w:1)
+	/// Storage: KittiesModule KittyCreator (r:0 w:1)
+	/// Storage: KittiesModule KittyApprovals (r:0 w:1)
+	/// Storage: KittiesModule RentalOffers (r:0 w:1)
+	/// Storage: KittiesModule SiringOffers (r:0 w:1)
+	/// Storage: KittiesModule Kitties (r:3 w:1)
+	/// Storage: KittiesModule KittyPopulation (r:1 w:1)
+	/// Storage: KittiesModule TraitCounts (r:6 w:6)
+	/// Storage: KittiesModule NameDeposits (r:1 w:1)
+	/// Storage: KittiesModule KittyParents (r:1 w:1)
+	/// Storage: KittiesModule KittyChildren (r:1 w:2)
+	/// Storage: KittiesModule KittyGeneration (r:0 w:1)
+	/// Storage: KittiesModule Provenance (r:0 w:1)
+	/// Storage: KittiesModule LastBred (r:0 w:1)
+	/// Storage: KittiesModule Stats (r:0 w:1)
+	fn burn() -> Weight {
+		Weight::from_parts(86_000_000, 18_000)
+			.saturating_add(T::DbWeight::get().reads(26_u64))
+			.saturating_add(T::DbWeight::get().writes(29_u64))
+	}
+	/// Storage: KittiesModule Kitties (r:1 w:1)
+	/// Storage: KittiesModule KittyOwner (r:1 w:0)
+	/// Storage: KittiesModule NameDeposits (r:1 w:1)
+	/// Storage: System Account (r:2 w:2)
+	fn rename() -> Weight {
+		Weight::from_parts(34_000_000, 8_000)
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
+	/// Storage: KittiesModule KittyOwner (r:1 w:0)
+	/// Storage: KittiesModule OperatorApprovals (r:1 w:0)
+	/// Storage: KittiesModule KittyApprovals (r:0 w:1)
+	fn approve() -> Weight {
+		Weight::from_parts(18_000_000, 5_000)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: KittiesModule OperatorApprovals (r:0 w:1)
+	fn set_approval_for_all() -> Weight {
+		Weight::from_parts(14_000_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: KittiesModule KittyOwner (r:1 w:1)
+	/// Storage: KittiesModule KittyApprovals (r:1 w:1)
+	/// Storage: KittiesModule OperatorApprovals (r:1 w:0)
+	/// Storage: KittiesModule Auctions (r:1 w:0)
+	/// Storage: KittiesModule Rentals (r:1 w:0)
+	/// Storage: KittiesModule Challenges (r:1 w:0)
+	/// Storage: KittiesModule ChallengingKitties (r:1 w:0)
+	/// Storage: KittiesModule OwnedKitties (r:2 w:2)
+	/// Storage: KittiesModule Provenance (r:1 w:1)
+	/// Storage: KittiesModule RentalOffers (r:0 w:1)
+	/// Storage: KittiesModule SiringOffers (r:0 w:1)
+	/// Storage: KittiesModule SaleExpiry (r:1 w:1)
+	/// Storage: KittiesModule SalesExpiring (r:1 w:1)
+	/// Storage: KittiesModule KittyOnSale (r:1 w:1)
+	fn transfer_from() -> Weight {
+		Weight::from_parts(40_000_000, 9_000)
+			.saturating_add(T::DbWeight::get().reads(13_u64))
+			.saturating_add(T::DbWeight::get().writes(10_u64))
+	}
+	/// Storage: KittiesModule KittyOwner (r:1 w:0)
+	/// Storage: KittiesModule Auctions (r:1 w:0)
+	/// Storage: KittiesModule RentalOffers (r:0 w:1)
+	/// Storage: KittiesModule Challenges (r:1 w:0)
+	/// Storage: KittiesModule ChallengingKitties (r:1 w:0)
+	fn offer_rental() -> Weight {
+		Weight::from_parts(19_000_000, 5_000)
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: KittiesModule KittyOwner (r:1 w:0)
+	/// Storage: KittiesModule RentalOffers (r:1 w:1)
+	fn cancel_rental_offer() -> Weight {
+		Weight::from_parts(16_000_000, 5_000)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: KittiesModule KittyOwner (r:1 w:0)
+	/// Storage: KittiesModule RentalOffers (r:1 w:0)
+	/// Storage: KittiesModule Rentals (r:1 w:1)
+	/// Storage: KittiesModule Auctions (r:1 w:0)
+	/// Storage: KittiesModule KittyOnSale (r:1 w:0)
+	/// Storage: System Account (r:2 w:2)
+	/// Storage: KittiesModule RentalsEnding (r:1 w:1)
+	/// Storage: KittiesModule Challenges (r:1 w:0)
+	/// Storage: KittiesModule ChallengingKitties (r:1 w:0)
+	fn rent() -> Weight {
+		Weight::from_parts(45_000_000, 8_000)
+			.saturating_add(T::DbWeight::get().reads(10_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
+	/// Storage: KittiesModule KittyOwner (r:1 w:0)
+	/// Storage: KittiesModule SiringOffers (r:0 w:1)
+	fn offer_siring() -> Weight {
+		Weight::from_parts(16_000_000, 3_000)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: KittiesModule KittyOwner (r:1 w:0)
+	/// Storage: KittiesModule SiringOffers (r:1 w:1)
+	fn cancel_siring_offer() -> Weight {
+		Weight::from_parts(16_000_000, 5_000)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: KittiesModule Rentals (r:2 w:0)
+	/// Storage: KittiesModule KittyOwner (r:2 w:1)
+	/// Storage: KittiesModule SiringOffers (r:1 w:0)
+	/// Storage: KittiesModule Kitties (r:2 w:1)
+	/// Storage: Babe Randomness (r:1 w:0)
+	/// Storage: Babe EpochStart (r:1 w:0)
+	/// Storage: System Account (r:3 w:3)
+	/// Storage: KittiesModule NextKittyId (r:1 w:1)
+	/// Storage: KittiesModule KittyCreator (r:0 w:1)
+	/// Storage: KittiesModule MintPrices (r:0 w:1)
+	/// Storage: KittiesModule OwnedKitties (r:1 w:1)
+	/// Storage: KittiesModule KittyPopulation (r:1 w:1)
+	/// Storage: KittiesModule TraitCounts (r:6 w:6)
+	/// Storage: KittiesModule Provenance (r:1 w:1)
+	/// Storage: KittiesModule NameDeposits (r:0 w:1)
+	/// Storage: KittiesModule KittyParents (r:0 w:1)
+	/// Storage: KittiesModule KittyChildren (r:0 w:2)
+	/// Storage: KittiesModule KittyGeneration (r:2 w:1)
+	/// Storage: KittiesModule LastBred (r:2 w:2)
+	fn breed_with_sire() -> Weight {
+		Weight::from_parts(90_000_000, 21_000)
+			.saturating_add(T::DbWeight::get().reads(26_u64))
+			.saturating_add(T::DbWeight::get().writes(24_u64))
+	}
+	/// Storage: KittiesModule Rentals (r:2 w:0)
+	/// Storage: KittiesModule KittyOwner (r:2 w:0)
+	/// Storage: KittiesModule Commitments (r:1 w:1)
+	/// Storage: KittiesModule CommitmentsExpiring (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	fn commit_mint() -> Weight {
+		Weight::from_parts(38_000_000, 9_000)
+			.saturating_add(T::DbWeight::get().reads(7_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	/// Storage: KittiesModule Commitments (r:1 w:1)
+	/// Storage: KittiesModule CommitmentsExpiring (r:1 w:1)
+	/// Storage: System BlockHash (r:1 w:0)
+	/// Storage: KittiesModule Rentals (r:2 w:0)
+	/// Storage: KittiesModule KittyOwner (r:2 w:1)
+	/// Storage: System Account (r:2 w:2)
+	/// Storage: KittiesModule Kitties (r:2 w:1)
+	/// Storage: KittiesModule LastBred (r:2 w:2)
+	/// Storage: KittiesModule KittyGeneration (r:2 w:1)
+	/// Storage: KittiesModule NextKittyId (r:1 w:1)
+	/// Storage: KittiesModule OwnedKitties (r:1 w:1)
+	/// Storage: KittiesModule KittyPopulation (r:1 w:1)
+	/// Storage: KittiesModule TraitCounts (r:6 w:6)
+	/// Storage: KittiesModule KittyParents (r:0 w:1)
+	/// Storage: KittiesModule NameDeposits (r:0 w:1)
+	/// Storage: KittiesModule KittyCreator (r:0 w:1)
+	/// Storage: KittiesModule Provenance (r:1 w:1)
+	/// Storage: KittiesModule KittyChildren (r:0 w:2)
+	/// Storage: KittiesModule MintPrices (r:0 w:1)
+	fn reveal_mint() -> Weight {
+		Weight::from_parts(103_000_000, 24_000)
+			.saturating_add(T::DbWeight::get().reads(25_u64))
+			.saturating_add(T::DbWeight::get().writes(25_u64))
+	}
+	/// Storage: KittiesModule Rentals (r:2 w:0)
+	/// Storage: KittiesModule KittyOwner (r:2 w:0)
+	/// Storage: KittiesModule Challenges (r:2 w:1)
+	/// Storage: KittiesModule ChallengingKitties (r:2 w:1)
+	/// Storage: KittiesModule Auctions (r:2 w:0)
+	/// Storage: System Account (r:1 w:1)
+	fn challenge() -> Weight {
+		Weight::from_parts(40_000_000, 8_000)
+			.saturating_add(T::DbWeight::get().reads(11_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	/// Storage: KittiesModule Rentals (r:1 w:0)
+	/// Storage: KittiesModule KittyOwner (r:1 w:0)
+	/// Storage: KittiesModule Challenges (r:1 w:0)
+	/// Storage: KittiesModule AcceptedChallenges (r:1 w:1)
+	/// Storage: KittiesModule BattlesDue (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	fn accept_challenge() -> Weight {
+		Weight::from_parts(38_000_000, 8_000)
+			.saturating_add(T::DbWeight::get().reads(6_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	/// Storage: KittiesModule Challenges (r:1 w:1)
+	/// Storage: KittiesModule Rentals (r:1 w:0)
+	/// Storage: KittiesModule AcceptedChallenges (r:1 w:0)
+	/// Storage: KittiesModule ChallengingKitties (r:0 w:1)
+	/// Storage: System Account (r:1 w:1)
+	fn cancel_challenge() -> Weight {
+		Weight::from_parts(30_000_000, 6_000)
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	/// Storage: KittiesModule KittyOwner (r:1 w:1)
+	/// Storage: KittiesModule Auctions (r:1 w:0)
+	/// Storage: KittiesModule Rentals (r:1 w:0)
+	/// Storage: KittiesModule Challenges (r:1 w:0)
+	/// Storage: KittiesModule ChallengingKitties (r:1 w:0)
+	/// Storage: KittiesModule OwnedKitties (r:2 w:2)
+	/// Storage: KittiesModule Provenance (r:1 w:1)
+	/// Storage: KittiesModule KittyApprovals (r:0 w:1)
+	/// Storage: KittiesModule RentalOffers (r:0 w:1)
+	/// Storage: KittiesModule SiringOffers (r:0 w:1)
+	/// Storage: KittiesModule SaleExpiry (r:1 w:1)
+	/// Storage: KittiesModule SalesExpiring (r:1 w:1)
+	/// Storage: KittiesModule KittyOnSale (r:1 w:1)
+	/// The range of component `n` is `[1, 50]`.
+	fn batch_transfer(n: u32, ) -> Weight {
+		Weight::from_parts(4_000_000, 0)
+			.saturating_add(Weight::from_parts(35_000_000, 8_000).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads((11_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes((10_u64).saturating_mul(n.into())))
+	}
+	/// Storage: KittiesModule Kitties (r:1 w:0)
+	/// Storage: KittiesModule KittyOwner (r:1 w:0)
+	/// Storage: KittiesModule KittyOnSale (r:1 w:1)
+	/// Storage: KittiesModule Auctions (r:1 w:0)
+	/// Storage: KittiesModule Rentals (r:1 w:0)
+	/// Storage: KittiesModule Challenges (r:1 w:0)
+	/// Storage: KittiesModule ChallengingKitties (r:1 w:0)
+	/// The range of component `n` is `[1, 50]`.
+	fn batch_sale(n: u32, ) -> Weight {
+		Weight::from_parts(3_000_000, 0)
+			.saturating_add(Weight::from_parts(22_000_000, 6_000).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads((7_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(n.into())))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	fn create() -> Weight {
+		Weight::from_parts(66_000_000, 7_000)
+			.saturating_add(RocksDbWeight::get().reads(14_u64))
+			.saturating_add(RocksDbWeight::get().writes(17_u64))
+	}
+	fn breed() -> Weight {
+		Weight::from_parts(85_000_000, 18_000)
+			.saturating_add(RocksDbWeight::get().reads(24_u64))
+			.saturating_add(RocksDbWeight::get().writes(23_u64))
+	}
+	fn transfer() -> Weight {
+		Weight::from_parts(36_000_000, 8_000)
+			.saturating_add(RocksDbWeight::get().reads(11_u64))
+			.saturating_add(RocksDbWeight::get().writes(10_u64))
+	}
+	fn sale() -> Weight {
+		Weight::from_parts(26_000_000, 6_000)
+			.saturating_add(RocksDbWeight::get().reads(8_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	fn buy() -> Weight {
+		Weight::from_parts(82_000_000, 12_000)
+			.saturating_add(RocksDbWeight::get().reads(16_u64))
+			.saturating_add(RocksDbWeight::get().writes(14_u64))
+	}
+	fn update_price() -> Weight {
+		Weight::from_parts(20_000_000, 4_000)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	fn create_auction() -> Weight {
+		Weight::from_parts(28_000_000, 6_000)
+			.saturating_add(RocksDbWeight::get().reads(8_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	fn bid() -> Weight {
+		Weight::from_parts(40_000_000, 6_000)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	fn settle_auction() -> Weight {
+		Weight::from_parts(76_000_000, 9_000)
+			.saturating_add(RocksDbWeight::get().reads(9_u64))
+			.saturating_add(RocksDbWeight::get().writes(12_u64))
+	}
+	fn fight_battle() -> Weight {
+		Weight::from_parts(58_000_000, 12_000)
+			.saturating_add(RocksDbWeight::get().reads(11_u64))
+			.saturating_add(RocksDbWeight::get().writes(7_u64))
+	}
+	fn make_offer() -> Weight {
+		Weight::from_parts(72_000_000, 12_000)
+			.saturating_add(RocksDbWeight::get().reads(22_u64))
+			.saturating_add(RocksDbWeight::get().writes(21_u64))
+	}
+	fn withdraw_offer() -> Weight {
+		Weight::from_parts(60_000_000, 11_000)
+			.saturating_add(RocksDbWeight::get().reads(21_u64))
+			.saturating_add(RocksDbWeight::get().writes(21_u64))
+	}
+	fn accept_offer() -> Weight {
+		Weight::from_parts(116_000_000, 16_000)
+			.saturating_add(RocksDbWeight::get().reads(36_u64))
+			.saturating_add(RocksDbWeight::get().writes(34_u64))
+	}
+	fn cancel_sale() -> Weight {
+		Weight::from_parts(22_000_000, 6_000)
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	fn burn() -> Weight {
+		Weight::from_parts(86_000_000, 18_000)
+			.saturating_add(RocksDbWeight::get().reads(26_u64))
+			.saturating_add(RocksDbWeight::get().writes(29_u64))
+	}
+	fn rename() -> Weight {
+		Weight::from_parts(34_000_000, 8_000)
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
+	fn approve() -> Weight {
+		Weight::from_parts(18_000_000, 5_000)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	fn set_approval_for_all() -> Weight {
+		Weight::from_parts(14_000_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	fn transfer_from() -> Weight {
+		Weight::from_parts(40_000_000, 9_000)
+			.saturating_add(RocksDbWeight::get().reads(13_u64))
+			.saturating_add(RocksDbWeight::get().writes(10_u64))
+	}
+	fn offer_rental() -> Weight {
+		Weight::from_parts(19_000_000, 5_000)
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	fn cancel_rental_offer() -> Weight {
+		Weight::from_parts(16_000_000, 5_000)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	fn rent() -> Weight {
+		Weight::from_parts(45_000_000, 8_000)
+			.saturating_add(RocksDbWeight::get().reads(10_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
+	fn offer_siring() -> Weight {
+		Weight::from_parts(16_000_000, 3_000)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	fn cancel_siring_offer() -> Weight {
+		Weight::from_parts(16_000_000, 5_000)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	fn breed_with_sire() -> Weight {
+		Weight::from_parts(90_000_000, 21_000)
+			.saturating_add(RocksDbWeight::get().reads(26_u64))
+			.saturating_add(RocksDbWeight::get().writes(24_u64))
+	}
+	fn commit_mint() -> Weight {
+		Weight::from_parts(38_000_000, 9_000)
+			.saturating_add(RocksDbWeight::get().reads(7_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	fn reveal_mint() -> Weight {
+		Weight::from_parts(103_000_000, 24_000)
+			.saturating_add(RocksDbWeight::get().reads(25_u64))
+			.saturating_add(RocksDbWeight::get().writes(25_u64))
+	}
+	fn challenge() -> Weight {
+		Weight::from_parts(40_000_000, 8_000)
+			.saturating_add(RocksDbWeight::get().reads(11_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	fn accept_challenge() -> Weight {
+		Weight::from_parts(38_000_000, 8_000)
+			.saturating_add(RocksDbWeight::get().reads(6_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	fn cancel_challenge() -> Weight {
+		Weight::from_parts(30_000_000, 6_000)
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	fn batch_transfer(n: u32, ) -> Weight {
+		Weight::from_parts(4_000_000, 0)
+			.saturating_add(Weight::from_parts(35_000_000, 8_000).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads((11_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes((10_u64).saturating_mul(n.into())))
+	}
+	fn batch_sale(n: u32, ) -> Weight {
+		Weight::from_parts(3_000_000, 0)
+			.saturating_add(Weight::from_parts(22_000_000, 6_000).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads((7_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(n.into())))
+	}
+}
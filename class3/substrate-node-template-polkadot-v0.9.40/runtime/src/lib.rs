@@ -8,7 +8,6 @@ include!(concat!(env!("OUT_DIR"), "/wasm_binary.rs"));
 
 use pallet_grandpa::AuthorityId as GrandpaId;
 use sp_api::impl_runtime_apis;
-use sp_consensus_aura::sr25519::AuthorityId as AuraId;
 use sp_core::{crypto::KeyTypeId, OpaqueMetadata};
 use sp_runtime::{
 	create_runtime_str, generic, impl_opaque_keys,
@@ -87,7 +86,7 @@ pub mod opaque {
 
 	impl_opaque_keys! {
 		pub struct SessionKeys {
-			pub aura: Aura,
+			pub babe: Babe,
 			pub grandpa: Grandpa,
 		}
 	}
@@ -105,17 +104,17 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	//   `spec_version`, and `authoring_version` are the same between Wasm and native.
 	// This value is set to 100 to notify Polkadot-JS App (https://polkadot.js.org/apps) to use
 	//   the compatible custom types.
-	spec_version: 200,
+	spec_version: 201,
 	impl_version: 1,
 	apis: RUNTIME_API_VERSIONS,
-	transaction_version: 1,
+	transaction_version: 2,
 	state_version: 1,
 };
 
 /// This determines the average expected block time that we are targeting.
 /// Blocks will be produced at a minimum duration defined by `SLOT_DURATION`.
 /// `SLOT_DURATION` is picked up by `pallet_timestamp` which is in turn picked
-/// up by `pallet_aura` to implement `fn slot_duration()`.
+/// up by `pallet_babe` to implement `fn slot_duration()`.
 ///
 /// Change this to adjust the block time.
 pub const MILLISECS_PER_BLOCK: u64 = 6000;
@@ -129,6 +128,19 @@ pub const MINUTES: BlockNumber = 60_000 / (MILLISECS_PER_BLOCK as BlockNumber);
 pub const HOURS: BlockNumber = MINUTES * 60;
 pub const DAYS: BlockNumber = HOURS * 24;
 
+/// The length of a BABE epoch. Kitty randomness is taken from the VRF outputs of the epoch
+/// before the previous one, so DNA can not be predicted within this window.
+pub const EPOCH_DURATION_IN_BLOCKS: BlockNumber = 10 * MINUTES;
+pub const EPOCH_DURATION_IN_SLOTS: u64 = EPOCH_DURATION_IN_BLOCKS as u64;
+
+/// The BABE epoch configuration at genesis: a quarter of the slots have a primary author, the
+/// rest are filled by secondary plain slots.
+pub const BABE_GENESIS_EPOCH_CONFIG: sp_consensus_babe::BabeEpochConfiguration =
+	sp_consensus_babe::BabeEpochConfiguration {
+		c: (1, 4),
+		allowed_slots: sp_consensus_babe::AllowedSlots::PrimaryAndSecondaryPlainSlots,
+	};
+
 /// The version information used to identify this runtime when compiled natively.
 #[cfg(feature = "std")]
 pub fn native_version() -> NativeVersion {
@@ -205,10 +217,17 @@ impl frame_system::Config for Runtime {
 	type MaxConsumers = frame_support::traits::ConstU32<16>;
 }
 
-impl pallet_aura::Config for Runtime {
-	type AuthorityId = AuraId;
+impl pallet_babe::Config for Runtime {
+	type EpochDuration = ConstU64<EPOCH_DURATION_IN_SLOTS>;
+	type ExpectedBlockTime = ConstU64<MILLISECS_PER_BLOCK>;
+	// There is no session pallet, so the genesis authorities are kept forever.
+	type EpochChangeTrigger = pallet_babe::SameAuthoritiesForever;
 	type DisabledValidators = ();
+	type WeightInfo = ();
 	type MaxAuthorities = ConstU32<32>;
+
+	type KeyOwnerProof = sp_core::Void;
+	type EquivocationReportSystem = ();
 }
 
 impl pallet_grandpa::Config for Runtime {
@@ -225,7 +244,7 @@ impl pallet_grandpa::Config for Runtime {
 impl pallet_timestamp::Config for Runtime {
 	/// A timestamp: milliseconds since the unix epoch.
 	type Moment = u64;
-	type OnTimestampSet = Aura;
+	type OnTimestampSet = Babe;
 	type MinimumPeriod = ConstU64<{ SLOT_DURATION / 2 }>;
 	type WeightInfo = ();
 }
@@ -273,18 +292,48 @@ impl pallet_template::Config for Runtime {
 parameter_types! {
 	pub KittyPalletId: PalletId = PalletId(*b"py/kitty");
 	pub KittyPrice: Balance = EXISTENTIAL_DEPOSIT * 10;
+	pub KittyBurnRefund: Perbill = Perbill::from_percent(50);
+	pub KittyMarketplaceFee: Perbill = Perbill::from_perthousand(25);
+	pub KittyCreatorRoyalty: Perbill = Perbill::from_percent(5);
+	pub KittyCommitmentForfeit: Perbill = Perbill::from_percent(20);
+	pub KittyNameDepositPerByte: Balance = EXISTENTIAL_DEPOSIT;
 }
 
 impl pallet_kitties::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
-	type Randomness = pallet_insecure_randomness_collective_flip::Pallet<Runtime>;
+	type Randomness = pallet_babe::ParentBlockRandomness<Runtime>;
 	type Currency = Balances;
 	type KittyPrice = KittyPrice;
 	type PalletId = KittyPalletId;
+	type BaseBreedingCooldown = ConstU32<{ 10 * MINUTES }>;
+	type BreedingCooldownPerGeneration = ConstU32<{ 10 * MINUTES }>;
+	type MaxBreedingCooldown = ConstU32<{ DAYS }>;
+	type MaxNameLength = ConstU32<32>;
+	type NameDepositPerByte = KittyNameDepositPerByte;
+	type MarketplaceFee = KittyMarketplaceFee;
+	type CreatorRoyalty = KittyCreatorRoyalty;
+	type BurnRefund = KittyBurnRefund;
+	type MaxKittiesOwned = ConstU32<100>;
+	type MaxAuctionsPerBlock = ConstU32<50>;
+	type MaxOffersPerKitty = ConstU32<20>;
+	type MaxListingsExpiringPerBlock = ConstU32<50>;
+	type MaxRentalsEndingPerBlock = ConstU32<50>;
+	type RevealDelay = ConstU32<2>;
+	type CommitmentTimeout = ConstU32<{ HOURS }>;
+	type CommitmentForfeit = KittyCommitmentForfeit;
+	type MaxCommitmentsExpiringPerBlock = ConstU32<50>;
+	type MaxBatch = ConstU32<50>;
+	type ExperiencePerWin = ConstU32<10>;
+	type ExperiencePerLevel = ConstU32<100>;
+	type MaxBattlesPerBlock = ConstU32<50>;
+	type MaxProvenanceLength = ConstU32<20>;
+	type OnKittyCreated = ();
+	type OnKittyTransfer = ();
+	// `pallet_kitties::weights` holds estimates, not benchmark results. Switch to its
+	// `SubstrateWeight<Runtime>` once the file has been regenerated with the benchmark CLI.
+	type WeightInfo = ();
 }
 
-impl pallet_insecure_randomness_collective_flip::Config for Runtime {}
-
 // Create the runtime by composing the FRAME pallets that were previously configured.
 construct_runtime!(
 	pub struct Runtime
@@ -295,7 +344,7 @@ construct_runtime!(
 	{
 		System: frame_system,
 		Timestamp: pallet_timestamp,
-		Aura: pallet_aura,
+		Babe: pallet_babe,
 		Grandpa: pallet_grandpa,
 		Balances: pallet_balances,
 		TransactionPayment: pallet_transaction_payment,
@@ -329,6 +378,12 @@ pub type UncheckedExtrinsic =
 	generic::UncheckedExtrinsic<Address, RuntimeCall, Signature, SignedExtra>;
 /// The payload being signed in transactions.
 pub type SignedPayload = generic::SignedPayload<RuntimeCall, SignedExtra>;
+/// Storage migrations to run on the next runtime upgrade.
+pub type Migrations = pallet_kitties::migrations::Migrations<Runtime>;
+
+/// A kitty as stored by this runtime.
+pub type Kitty = pallet_kitties::KittyOf<Runtime>;
+
 /// Executive: handles dispatch to the various modules.
 pub type Executive = frame_executive::Executive<
 	Runtime,
@@ -336,6 +391,7 @@ pub type Executive = frame_executive::Executive<
 	frame_system::ChainContext<Runtime>,
 	Runtime,
 	AllPalletsWithSystem,
+	Migrations,
 >;
 
 #[cfg(feature = "runtime-benchmarks")]
@@ -350,6 +406,7 @@ mod benches {
 		[pallet_balances, Balances]
 		[pallet_timestamp, Timestamp]
 		[pallet_template, TemplateModule]
+		[pallet_kitties, KittiesModule]
 	);
 }
 
@@ -411,13 +468,45 @@ impl_runtime_apis! {
 		}
 	}
 
-	impl sp_consensus_aura::AuraApi<Block, AuraId> for Runtime {
-		fn slot_duration() -> sp_consensus_aura::SlotDuration {
-			sp_consensus_aura::SlotDuration::from_millis(Aura::slot_duration())
+	impl sp_consensus_babe::BabeApi<Block> for Runtime {
+		fn configuration() -> sp_consensus_babe::BabeConfiguration {
+			let epoch_config = Babe::epoch_config().unwrap_or(BABE_GENESIS_EPOCH_CONFIG);
+			sp_consensus_babe::BabeConfiguration {
+				slot_duration: Babe::slot_duration(),
+				epoch_length: EPOCH_DURATION_IN_SLOTS,
+				c: epoch_config.c,
+				authorities: Babe::authorities().to_vec(),
+				randomness: Babe::randomness(),
+				allowed_slots: epoch_config.allowed_slots,
+			}
+		}
+
+		fn current_epoch_start() -> sp_consensus_babe::Slot {
+			Babe::current_epoch_start()
 		}
 
-		fn authorities() -> Vec<AuraId> {
-			Aura::authorities().into_inner()
+		fn current_epoch() -> sp_consensus_babe::Epoch {
+			Babe::current_epoch()
+		}
+
+		fn next_epoch() -> sp_consensus_babe::Epoch {
+			Babe::next_epoch()
+		}
+
+		fn generate_key_ownership_proof(
+			_slot: sp_consensus_babe::Slot,
+			_authority_id: sp_consensus_babe::AuthorityId,
+		) -> Option<sp_consensus_babe::OpaqueKeyOwnershipProof> {
+			// NOTE: like GRANDPA, our key owner proof type is a bottom type, so there is
+			// never a proof to generate.
+			None
+		}
+
+		fn submit_report_equivocation_unsigned_extrinsic(
+			_equivocation_proof: sp_consensus_babe::EquivocationProof<<Block as BlockT>::Header>,
+			_key_owner_proof: sp_consensus_babe::OpaqueKeyOwnershipProof,
+		) -> Option<()> {
+			None
 		}
 	}
 
@@ -513,6 +602,58 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl pallet_kitties_rpc_runtime_api::KittiesApi<Block, AccountId, Balance, BlockNumber, Kitty>
+		for Runtime
+	{
+		fn kitty(kitty_id: pallet_kitties::KittyId) -> Option<Kitty> {
+			KittiesModule::kitties(kitty_id)
+		}
+
+		fn kitties_of(account: AccountId) -> Vec<pallet_kitties::KittyId> {
+			KittiesModule::kitties_of(&account)
+		}
+
+		fn parents(
+			kitty_id: pallet_kitties::KittyId,
+		) -> Option<(pallet_kitties::KittyId, pallet_kitties::KittyId)> {
+			KittiesModule::kitty_parents(kitty_id)
+		}
+
+		fn on_sale() -> Vec<(pallet_kitties::KittyId, Balance)> {
+			KittiesModule::on_sale()
+		}
+
+		fn next_kitty_id() -> pallet_kitties::KittyId {
+			KittiesModule::next_kitty_id()
+		}
+
+		fn breeding_cooldown(kitty_id: pallet_kitties::KittyId) -> BlockNumber {
+			KittiesModule::breeding_cooldown_remaining(kitty_id)
+		}
+
+		fn kitty_traits(kitty_id: pallet_kitties::KittyId) -> Option<pallet_kitties::KittyTraits> {
+			KittiesModule::traits_of(kitty_id)
+		}
+
+		fn rarity_score(kitty_id: pallet_kitties::KittyId) -> Option<u64> {
+			KittiesModule::rarity_score(kitty_id)
+		}
+
+		fn provenance(
+			kitty_id: pallet_kitties::KittyId,
+		) -> Vec<pallet_kitties::ProvenanceRecord<AccountId, BlockNumber>> {
+			KittiesModule::provenance(kitty_id).into_inner()
+		}
+
+		fn ancestors(kitty_id: pallet_kitties::KittyId, depth: u32) -> Vec<pallet_kitties::KittyId> {
+			KittiesModule::ancestors(kitty_id, depth)
+		}
+
+		fn descendants(kitty_id: pallet_kitties::KittyId) -> Vec<pallet_kitties::KittyId> {
+			KittiesModule::descendants(kitty_id)
+		}
+	}
+
 	#[cfg(feature = "runtime-benchmarks")]
 	impl frame_benchmarking::Benchmark<Block> for Runtime {
 		fn benchmark_metadata(extra: bool) -> (